@@ -3,7 +3,17 @@ use std::io;
 
 fn main() {
 	if let Err(err) = (|| {
-		Automaton::load_from_simple_text(&mut io::stdin().lock())?.store_as_dot(&mut io::stdout())
+		let mut args = std::env::args().skip(1).peekable();
+		let suffixed = args.next_if(|arg| arg == "--suffixed").is_some();
+		if args.next().is_some() {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"usage: automaton2dot [--suffixed]",
+			));
+		}
+		Automaton::load(&mut io::stdin().lock(), suffixed)
+			.map_err(|err| err.with_file("{stdin}"))?
+			.store_as_dot(&mut io::stdout())
 	})() {
 		eprintln!("Error: {}", err);
 		std::process::exit(1);
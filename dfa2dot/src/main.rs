@@ -2,9 +2,11 @@ use fsm::DFA;
 use std::io;
 
 fn main() {
-	if let Err(err) =
-		(|| DFA::load_from_simple_text(&mut io::stdin().lock())?.store_as_dot(&mut io::stdout()))()
-	{
+	if let Err(err) = (|| {
+		DFA::load_from_simple_text(&mut io::stdin().lock())
+			.map_err(|err| err.with_file("{stdin}"))?
+			.store_as_dot(&mut io::stdout())
+	})() {
 		eprintln!("Error: {}", err);
 		std::process::exit(1);
 	}
@@ -0,0 +1,140 @@
+use super::{Automaton, StateType, EPSILON};
+use crate::common::Symbols;
+use std::collections::BTreeSet;
+use std::io::{Error, ErrorKind, Result};
+
+/// Builds an [`Automaton`] from named states, [`EPSILON`] marking epsilon transitions.
+///
+/// ```
+/// use fsm::{AutomatonBuilder, EPSILON};
+///
+/// let mut builder = AutomatonBuilder::new();
+/// builder.add_input('a')?.set_initial("A").set_final("C");
+/// builder.add_transition("A", 'a', "B").add_transition("B", EPSILON, "A");
+/// builder.add_transition("B", 'b', "C");
+/// let automaton = builder.build();
+/// assert!(automaton.accepts("ab"));
+/// assert!(automaton.accepts("aaab"));
+/// assert!(!automaton.accepts("b"));
+/// let a = automaton.state_id("A").unwrap();
+/// let b = automaton.state_id("B").unwrap();
+/// assert_eq!(automaton.successors(a, 'a').into_iter().collect::<Vec<_>>(), [b]);
+/// assert_eq!(automaton.successors(b, EPSILON).into_iter().collect::<Vec<_>>(), [a]);
+/// assert!(AutomatonBuilder::new().add_input(EPSILON).is_err());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Default)]
+pub struct AutomatonBuilder {
+	states: Symbols<String>,
+	state_types: Vec<StateType>,
+	inputs: Symbols<char>,
+	transitions: BTreeSet<(usize, usize, usize)>,
+	epsilon_transitions: BTreeSet<(usize, usize)>,
+}
+
+impl AutomatonBuilder {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	fn state_id(&mut self, name: &str) -> usize {
+		let state = self.states.get_or_create_id(name.to_string());
+		if state == self.state_types.len() {
+			self.state_types.push(StateType::default());
+		}
+		state
+	}
+
+	pub fn add_state(&mut self, name: &str) -> &mut Self {
+		self.state_id(name);
+		self
+	}
+
+	pub fn add_input(&mut self, input: char) -> Result<&mut Self> {
+		if input == EPSILON {
+			return Err(Error::new(
+				ErrorKind::InvalidInput,
+				"'ε' is reserved for epsilon transitions",
+			));
+		}
+		self.inputs.get_or_create_id(input);
+		Ok(self)
+	}
+
+	pub fn add_transition(&mut self, from: &str, on: char, into: &str) -> &mut Self {
+		let from = self.state_id(from);
+		let into = self.state_id(into);
+		if on == EPSILON {
+			self.epsilon_transitions.insert((from, into));
+		} else {
+			let on = self.inputs.get_or_create_id(on);
+			self.transitions.insert((from, on, into));
+		}
+		self
+	}
+
+	pub fn set_initial(&mut self, name: &str) -> &mut Self {
+		let state = self.state_id(name);
+		self.state_types[state] |= StateType::INITIAL;
+		self
+	}
+
+	pub fn set_final(&mut self, name: &str) -> &mut Self {
+		let state = self.state_id(name);
+		self.state_types[state] |= StateType::FINAL;
+		self
+	}
+
+	pub fn build(&self) -> Automaton {
+		Automaton {
+			states: self.states.table().to_vec(),
+			state_types: self.state_types.clone(),
+			inputs: self.inputs.table().to_vec(),
+			transitions: self.transitions.clone(),
+			epsilon_transitions: self.epsilon_transitions.clone(),
+		}
+	}
+}
+
+impl Automaton {
+	pub fn states(&self) -> impl ExactSizeIterator<Item = &str> {
+		self.states.iter().map(String::as_str)
+	}
+
+	pub fn state_id(&self, name: &str) -> Option<usize> {
+		self.states.iter().position(|state| state == name)
+	}
+
+	pub fn alphabet(&self) -> impl ExactSizeIterator<Item = char> + '_ {
+		self.inputs.iter().copied()
+	}
+
+	pub fn transitions(&self) -> impl Iterator<Item = (usize, char, usize)> + '_ {
+		self.transitions
+			.iter()
+			.map(move |&(from, on, into)| (from, self.inputs[on], into))
+			.chain(
+				self.epsilon_transitions
+					.iter()
+					.map(|&(from, into)| (from, EPSILON, into)),
+			)
+	}
+
+	pub fn successors(&self, state: usize, input: char) -> BTreeSet<usize> {
+		if input == EPSILON {
+			self.epsilon_transitions
+				.range((state, 0)..(state + 1, 0))
+				.map(|&(_, into)| into)
+				.collect()
+		} else {
+			match self.input_id(input) {
+				Some(on) => self
+					.transitions
+					.range((state, on, 0)..(state, on + 1, 0))
+					.map(|&(_, _, into)| into)
+					.collect(),
+				None => BTreeSet::new(),
+			}
+		}
+	}
+}
@@ -0,0 +1,76 @@
+use super::{Automaton, StateType};
+use crate::common::Symbols;
+
+fn sink_name(states: &[String]) -> String {
+	(states.len()..)
+		.map(|id| format!("S{}", id))
+		.find(|name| !states.contains(name))
+		.unwrap()
+}
+
+impl Automaton {
+	pub fn complete(&self, alphabet: Option<&[char]>) -> Self {
+		let mut inputs = Symbols::new();
+		for &input in self.inputs.iter().chain(alphabet.unwrap_or_default()) {
+			inputs.get_or_create_id(input);
+		}
+		let mut automaton = Self {
+			states: self.states.clone(),
+			state_types: self.state_types.clone(),
+			inputs: inputs.into_table(),
+			transitions: self.transitions.clone(),
+			epsilon_transitions: self.epsilon_transitions.clone(),
+		};
+		let sink = automaton.states.len();
+		let mut has_sink = false;
+		for from in 0..automaton.states.len() {
+			for on in 0..automaton.inputs.len() {
+				if automaton
+					.transitions
+					.range((from, on, 0)..(from, on + 1, 0))
+					.next()
+					.is_none()
+				{
+					automaton.transitions.insert((from, on, sink));
+					has_sink = true;
+				}
+			}
+		}
+		if has_sink {
+			automaton.states.push(sink_name(&self.states));
+			automaton.state_types.push(StateType::default());
+			for on in 0..automaton.inputs.len() {
+				automaton.transitions.insert((sink, on, sink));
+			}
+		}
+		automaton
+	}
+
+	/// Returns an automaton accepting exactly the words over the inputs and `alphabet` that
+	/// `self` rejects.
+	///
+	/// ```
+	/// use fsm::Automaton;
+	///
+	/// let empty = Automaton::load_from_simple_text(&mut "2\n1\nq0 a q1\n".as_bytes())?;
+	/// let all = empty.complement(None);
+	/// assert!(all.accepts("") && all.accepts("aa"));
+	/// let ab = Automaton::load_from_suffixed_simple_text(&mut "2\n1\nq0S a q1F\n".as_bytes())?;
+	/// let complement = ab.complement(Some(&['b']));
+	/// assert_eq!(complement.alphabet().collect::<Vec<_>>(), ['a', 'b']);
+	/// assert!(!complement.accepts("a"));
+	/// assert!(complement.accepts("") && complement.accepts("b") && complement.accepts("aa"));
+	/// # Ok::<(), fsm::LoadError>(())
+	/// ```
+	pub fn complement(&self, alphabet: Option<&[char]>) -> Self {
+		let mut automaton = self.determine();
+		if let Some(initial) = automaton.state_types.get_mut(0) {
+			*initial |= StateType::INITIAL;
+		}
+		let mut automaton = automaton.complete(alphabet);
+		for state_type in &mut automaton.state_types {
+			*state_type ^= StateType::FINAL;
+		}
+		automaton
+	}
+}
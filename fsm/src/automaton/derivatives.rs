@@ -0,0 +1,62 @@
+use super::regexp::regexp_inputs;
+use super::{generated_names, Automaton, StateType};
+use crate::common::Symbols;
+use crate::regexp::RegExp;
+use std::collections::BTreeSet;
+
+impl Automaton {
+	pub fn from_regexp_brzozowski(regexp: &RegExp, alphabet: &[char]) -> Self {
+		let inputs = regexp_inputs(regexp, alphabet).into_table();
+		Self::from_derivatives(regexp, inputs, |regexp, c, alphabet| {
+			match regexp.derivative(c, alphabet) {
+				RegExp::Empty => BTreeSet::new(),
+				derivative => BTreeSet::from([derivative]),
+			}
+		})
+	}
+
+	pub fn from_regexp_antimirov(regexp: &RegExp, alphabet: &[char]) -> Self {
+		let inputs = regexp_inputs(regexp, alphabet).into_table();
+		Self::from_derivatives(regexp, inputs, RegExp::partial_derivatives)
+	}
+
+	fn from_derivatives<F>(regexp: &RegExp, inputs: Vec<char>, derivatives: F) -> Self
+	where
+		F: Fn(&RegExp, char, &[char]) -> BTreeSet<RegExp>,
+	{
+		let mut states = Symbols::new();
+		let mut transitions = BTreeSet::new();
+		states.get_or_create_id(regexp.clone());
+		let mut from = 0;
+		while from < states.len() {
+			let state = states.get_name(from).clone();
+			for (on, &c) in inputs.iter().enumerate() {
+				for derivative in derivatives(&state, c, &inputs) {
+					transitions.insert((from, on, states.get_or_create_id(derivative)));
+				}
+			}
+			from += 1;
+		}
+		Self {
+			states: generated_names(states.len()),
+			state_types: states
+				.table()
+				.iter()
+				.enumerate()
+				.map(|(id, state)| {
+					let mut state_type = StateType::default();
+					if id == 0 {
+						state_type |= StateType::INITIAL;
+					}
+					if state.is_nullable() {
+						state_type |= StateType::FINAL;
+					}
+					state_type
+				})
+				.collect(),
+			inputs,
+			transitions,
+			epsilon_transitions: BTreeSet::new(),
+		}
+	}
+}
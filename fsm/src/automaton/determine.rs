@@ -20,22 +20,22 @@ fn get_or_create_state(
 	}
 }
 
-fn format_state_name(new_state: usize, old_states: &[usize], old_names: &[String]) -> String {
+fn merge_state_types(new_state: usize, old_states: &[usize], old_types: &[StateType]) -> StateType {
 	let mut state_type = StateType::default();
 	for &old_state in old_states {
-		state_type |= StateType::from_last_char(&old_names[old_state]);
+		state_type |= old_types[old_state];
 	}
 	if new_state != 0 {
 		state_type &= StateType::FINAL;
 	}
-	format!("S{}{}", new_state, state_type.name())
+	state_type
 }
 
 impl Automaton {
-	fn initial_states(&self) -> BTreeSet<usize> {
+	pub(super) fn initial_states(&self) -> BTreeSet<usize> {
 		let mut states = BTreeSet::new();
-		for (id, name) in self.states.iter().enumerate() {
-			if StateType::from_last_char(name) & StateType::INITIAL {
+		for id in 0..self.states.len() {
+			if self.is_initial(id) {
 				states.insert(id);
 			}
 		}
@@ -45,17 +45,22 @@ impl Automaton {
 	pub fn determine(&self) -> Self {
 		let mut automaton = Self {
 			states: Vec::new(),
+			state_types: Vec::new(),
 			inputs: self.inputs.clone(),
 			transitions: BTreeSet::new(),
+			epsilon_transitions: BTreeSet::new(),
 		};
 		let mut states = HashMap::new();
 		let mut queue = VecDeque::new();
-		get_or_create_state(&mut states, &mut queue, self.initial_states());
+		let mut initial_states = self.initial_states();
+		self.epsilon_closure(&mut initial_states);
+		get_or_create_state(&mut states, &mut queue, initial_states);
 		while let Some(old_states) = queue.pop_front() {
 			let from = automaton.states.len();
+			automaton.states.push(format!("S{}", from));
 			automaton
-				.states
-				.push(format_state_name(from, &old_states, &self.states));
+				.state_types
+				.push(merge_state_types(from, &old_states, &self.state_types));
 			let mut old_transitions = BTreeMap::new();
 			for &from in old_states.iter() {
 				for (_, on, into) in self.transitions.range((from, 0, 0)..(from + 1, 0, 0)) {
@@ -65,7 +70,8 @@ impl Automaton {
 						.insert(*into);
 				}
 			}
-			for (&on, into) in old_transitions {
+			for (&on, mut into) in old_transitions {
+				self.epsilon_closure(&mut into);
 				let into = get_or_create_state(&mut states, &mut queue, into);
 				automaton.transitions.insert((from, on, into));
 			}
@@ -1,4 +1,4 @@
-use super::{Automaton, StateType};
+use super::{Automaton, EPSILON};
 use std::io::{Result, Write};
 
 impl Automaton {
@@ -6,12 +6,11 @@ impl Automaton {
 		writer.write_all(
 			b"digraph {\n\trankdir=LR\n\tnode [shape=circle]\n\tstart [shape=point]\n",
 		)?;
-		for state in &self.states {
-			let state_type = StateType::from_last_char(state);
-			if state_type & StateType::INITIAL {
+		for (id, state) in self.states.iter().enumerate() {
+			if self.is_initial(id) {
 				writeln!(writer, "\tstart -> \"{}\"", state)?;
 			}
-			if state_type & StateType::FINAL {
+			if self.is_final(id) {
 				writeln!(writer, "\t\"{}\" [shape=doublecircle]", state)?;
 			}
 		}
@@ -22,6 +21,13 @@ impl Automaton {
 				self.states[*from], self.states[*into], self.inputs[*on]
 			)?;
 		}
+		for (from, into) in &self.epsilon_transitions {
+			writeln!(
+				writer,
+				"\t\"{}\" -> \"{}\" [label=\"{}\"]",
+				self.states[*from], self.states[*into], EPSILON
+			)?;
+		}
 		writer.write_all(b"}")?;
 		Ok(())
 	}
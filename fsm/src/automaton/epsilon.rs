@@ -0,0 +1,40 @@
+use super::{Automaton, StateType};
+use std::collections::BTreeSet;
+
+impl Automaton {
+	pub(super) fn epsilon_closure(&self, states: &mut BTreeSet<usize>) {
+		let mut stack: Vec<usize> = states.iter().copied().collect();
+		while let Some(from) = stack.pop() {
+			for &(_, into) in self.epsilon_transitions.range((from, 0)..(from + 1, 0)) {
+				if states.insert(into) {
+					stack.push(into);
+				}
+			}
+		}
+	}
+
+	pub fn remove_epsilons(&self) -> Self {
+		let mut automaton = Self {
+			states: self.states.clone(),
+			state_types: Vec::with_capacity(self.states.len()),
+			inputs: self.inputs.clone(),
+			transitions: BTreeSet::new(),
+			epsilon_transitions: BTreeSet::new(),
+		};
+		for (from, &state_type) in self.state_types.iter().enumerate() {
+			let mut closure = BTreeSet::from([from]);
+			self.epsilon_closure(&mut closure);
+			let mut new_type = state_type;
+			for &state in &closure {
+				for &(_, on, into) in self.transitions.range((state, 0, 0)..(state + 1, 0, 0)) {
+					automaton.transitions.insert((from, on, into));
+				}
+				if self.is_final(state) {
+					new_type |= StateType::FINAL;
+				}
+			}
+			automaton.state_types.push(new_type);
+		}
+		automaton
+	}
+}
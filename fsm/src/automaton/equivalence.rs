@@ -0,0 +1,102 @@
+use super::Automaton;
+use crate::common::Symbols;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+struct Subsets<'a> {
+	automata: [&'a Automaton; 2],
+	ids: HashMap<(usize, BTreeSet<usize>), usize>,
+	parents: Vec<usize>,
+}
+
+impl<'a> Subsets<'a> {
+	fn new(automata: [&'a Automaton; 2]) -> Self {
+		Self {
+			automata,
+			ids: HashMap::new(),
+			parents: Vec::new(),
+		}
+	}
+
+	fn get_or_create_id(&mut self, side: usize, states: BTreeSet<usize>) -> usize {
+		let next_id = self.parents.len();
+		match self.ids.entry((side, states)) {
+			Entry::Vacant(entry) => {
+				entry.insert(next_id);
+				self.parents.push(next_id);
+				next_id
+			}
+			Entry::Occupied(entry) => *entry.get(),
+		}
+	}
+
+	fn find(&mut self, mut id: usize) -> usize {
+		while self.parents[id] != id {
+			self.parents[id] = self.parents[self.parents[id]];
+			id = self.parents[id];
+		}
+		id
+	}
+
+	fn union(&mut self, lhs: &BTreeSet<usize>, rhs: &BTreeSet<usize>) -> bool {
+		let lhs = self.get_or_create_id(0, lhs.clone());
+		let rhs = self.get_or_create_id(1, rhs.clone());
+		let (lhs, rhs) = (self.find(lhs), self.find(rhs));
+		self.parents[lhs] = rhs;
+		lhs != rhs
+	}
+
+	fn is_distinguished(&self, lhs: &BTreeSet<usize>, rhs: &BTreeSet<usize>) -> bool {
+		self.automata[0].is_accepting(lhs) != self.automata[1].is_accepting(rhs)
+	}
+}
+
+pub(super) fn format_word(word_parts: &[(usize, char)], mut part: usize) -> String {
+	let mut word = Vec::new();
+	while part != 0 {
+		let (parent, c) = word_parts[part];
+		word.push(c);
+		part = parent;
+	}
+	word.into_iter().rev().collect()
+}
+
+impl Automaton {
+	pub fn equivalence_counterexample(&self, other: &Self) -> Option<String> {
+		let mut inputs = Symbols::new();
+		for &input in self.inputs.iter().chain(&other.inputs) {
+			inputs.get_or_create_id(input);
+		}
+		let inputs: Vec<(char, Option<usize>, Option<usize>)> = inputs
+			.into_table()
+			.into_iter()
+			.map(|c| (c, self.input_id(c), other.input_id(c)))
+			.collect();
+		let mut subsets = Subsets::new([self, other]);
+		let start = (self.initial_closure(), other.initial_closure());
+		if subsets.is_distinguished(&start.0, &start.1) {
+			return Some(String::new());
+		}
+		subsets.union(&start.0, &start.1);
+		let mut word_parts = vec![(0, '\0')];
+		let mut queue = VecDeque::from([(start, 0)]);
+		while let Some(((lhs, rhs), part)) = queue.pop_front() {
+			for &(c, lhs_on, rhs_on) in &inputs {
+				let lhs = lhs_on.map_or_else(BTreeSet::new, |on| self.step(&lhs, on));
+				let rhs = rhs_on.map_or_else(BTreeSet::new, |on| other.step(&rhs, on));
+				if subsets.union(&lhs, &rhs) {
+					word_parts.push((part, c));
+					if subsets.is_distinguished(&lhs, &rhs) {
+						return Some(format_word(&word_parts, word_parts.len() - 1));
+					}
+					queue.push_back(((lhs, rhs), word_parts.len() - 1));
+				}
+			}
+		}
+		None
+	}
+
+	pub fn is_equivalent(&self, other: &Self) -> bool {
+		self.equivalence_counterexample(other).is_none()
+	}
+}
@@ -0,0 +1,86 @@
+use super::{generated_names, Automaton, StateType};
+use crate::regexp::{ParseRegExpError, RegExp};
+use std::collections::BTreeSet;
+
+impl Automaton {
+	pub fn from_extended_regexp(regexp: &str) -> Result<Self, ParseRegExpError> {
+		Self::from_extended_regexp_with_alphabet(regexp, &[])
+	}
+
+	pub fn from_extended_regexp_with_alphabet(
+		regexp: &str,
+		alphabet: &[char],
+	) -> Result<Self, ParseRegExpError> {
+		Ok(Self::from_parsed_regexp(
+			&RegExp::parse_extended(regexp)?,
+			alphabet,
+		))
+	}
+
+	pub(super) fn compose_regexp(regexp: &RegExp, alphabet: &[char]) -> Self {
+		let compose = |regexp| Self::compose_regexp(regexp, alphabet);
+		match regexp {
+			RegExp::Empty => Self::single_state(alphabet, StateType::INITIAL),
+			RegExp::Epsilon => Self::single_state(alphabet, StateType::INITIAL | StateType::FINAL),
+			RegExp::Concat(parts) => parts
+				.iter()
+				.map(compose)
+				.reduce(|automaton, part| automaton.concat(&part))
+				.unwrap_or_else(|| compose(&RegExp::Epsilon)),
+			RegExp::Alternation(variants) => variants
+				.iter()
+				.map(compose)
+				.reduce(|automaton, variant| automaton.alternate(&variant))
+				.unwrap_or_else(|| compose(&RegExp::Empty)),
+			RegExp::Intersection(operands) => operands
+				.iter()
+				.map(compose)
+				.reduce(|automaton, operand| automaton.intersect(&operand))
+				.unwrap_or_else(|| compose(&RegExp::Empty).complement(Some(alphabet))),
+			RegExp::Complement(inner) => compose(inner).complement(Some(alphabet)),
+			RegExp::Star(inner) => compose(inner).star(),
+			RegExp::Plus(inner) => compose(inner).plus(),
+			RegExp::Optional(inner) => compose(inner).optional(),
+			RegExp::Repeat(..) if regexp.is_empty_repeat() => compose(&RegExp::Empty),
+			RegExp::Repeat(inner, min, max) => {
+				let automaton = compose(inner);
+				let mut repeated = compose(&RegExp::Epsilon);
+				for _ in 0..*min {
+					repeated = repeated.concat(&automaton);
+				}
+				match max {
+					Some(max) => {
+						for _ in *min..*max {
+							repeated = repeated.concat(&automaton.optional());
+						}
+					}
+					None => repeated = repeated.concat(&automaton.star()),
+				}
+				repeated
+			}
+			class => {
+				let chars = class.matched_chars(alphabet).unwrap();
+				Self {
+					states: generated_names(2),
+					state_types: vec![StateType::INITIAL, StateType::FINAL],
+					inputs: alphabet.to_vec(),
+					transitions: chars
+						.iter()
+						.map(|c| (0, alphabet.iter().position(|a| a == c).unwrap(), 1))
+						.collect(),
+					epsilon_transitions: BTreeSet::new(),
+				}
+			}
+		}
+	}
+
+	fn single_state(alphabet: &[char], state_type: StateType) -> Self {
+		Self {
+			states: generated_names(1),
+			state_types: vec![state_type],
+			inputs: alphabet.to_vec(),
+			transitions: BTreeSet::new(),
+			epsilon_transitions: BTreeSet::new(),
+		}
+	}
+}
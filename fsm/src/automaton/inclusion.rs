@@ -0,0 +1,81 @@
+use super::equivalence::format_word;
+use super::{Automaton, StateType};
+use std::collections::{BTreeSet, VecDeque};
+
+struct Antichain {
+	subsets: Vec<Vec<BTreeSet<usize>>>,
+}
+
+impl Antichain {
+	fn new(n_states: usize) -> Self {
+		Self {
+			subsets: vec![Vec::new(); n_states],
+		}
+	}
+
+	fn insert(&mut self, state: usize, subset: &BTreeSet<usize>) -> bool {
+		let subsets = &mut self.subsets[state];
+		if subsets.iter().any(|smaller| smaller.is_subset(subset)) {
+			return false;
+		}
+		subsets.retain(|larger| !subset.is_subset(larger));
+		subsets.push(subset.clone());
+		true
+	}
+}
+
+impl Automaton {
+	pub fn inclusion_counterexample(&self, other: &Self) -> Option<String> {
+		let automaton = self.remove_epsilons();
+		let is_final = |state: usize| automaton.is_final(state);
+		let inputs: Vec<Option<usize>> = automaton
+			.inputs
+			.iter()
+			.map(|&c| other.input_id(c))
+			.collect();
+		let mut antichain = Antichain::new(automaton.states.len());
+		let mut word_parts = vec![(0, '\0')];
+		let mut queue = VecDeque::new();
+		let start = other.initial_closure();
+		for state in automaton.initial_states() {
+			if is_final(state) && !other.is_accepting(&start) {
+				return Some(String::new());
+			}
+			if antichain.insert(state, &start) {
+				queue.push_back((state, start.clone(), 0));
+			}
+		}
+		while let Some((from, subset, part)) = queue.pop_front() {
+			for &(_, on, into) in automaton.transitions.range((from, 0, 0)..(from + 1, 0, 0)) {
+				let subset = inputs[on].map_or_else(BTreeSet::new, |on| other.step(&subset, on));
+				if antichain.insert(into, &subset) {
+					word_parts.push((part, automaton.inputs[on]));
+					if is_final(into) && !other.is_accepting(&subset) {
+						return Some(format_word(&word_parts, word_parts.len() - 1));
+					}
+					queue.push_back((into, subset, word_parts.len() - 1));
+				}
+			}
+		}
+		None
+	}
+
+	pub fn is_subset_of(&self, other: &Self) -> bool {
+		self.inclusion_counterexample(other).is_none()
+	}
+
+	pub fn universality_counterexample(&self) -> Option<String> {
+		let universal = Self {
+			states: vec!["S0".to_string()],
+			state_types: vec![StateType::INITIAL | StateType::FINAL],
+			inputs: self.inputs.clone(),
+			transitions: (0..self.inputs.len()).map(|on| (0, on, 0)).collect(),
+			epsilon_transitions: BTreeSet::new(),
+		};
+		universal.inclusion_counterexample(self)
+	}
+
+	pub fn is_universal(&self) -> bool {
+		self.universality_counterexample().is_none()
+	}
+}
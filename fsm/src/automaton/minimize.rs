@@ -0,0 +1,7 @@
+use super::Automaton;
+
+impl Automaton {
+	pub fn minimize_brzozowski(&self) -> Self {
+		self.reverse().determine().reverse().determine()
+	}
+}
@@ -1,14 +1,38 @@
+mod builder;
+mod complete;
+mod derivatives;
 mod determine;
 mod dot;
+mod epsilon;
+mod equivalence;
+mod extended_regexp;
+mod inclusion;
+mod minimize;
+mod moore;
+mod operations;
+mod product;
 mod regexp;
 mod regular_grammar;
+mod run;
 mod simple_text;
 mod state;
+mod thompson;
+mod to_regexp;
+pub use builder::AutomatonBuilder;
+pub use regular_grammar::GrammarType;
 use state::StateType;
 use std::collections::BTreeSet;
 
+pub const EPSILON: char = 'ε';
+
+fn generated_names(n_states: usize) -> Vec<String> {
+	(0..n_states).map(|id| format!("S{}", id)).collect()
+}
+
 pub struct Automaton {
 	states: Vec<String>,
+	state_types: Vec<StateType>,
 	inputs: Vec<char>,
 	transitions: BTreeSet<(usize, usize, usize)>,
+	epsilon_transitions: BTreeSet<(usize, usize)>,
 }
@@ -0,0 +1,86 @@
+use super::{generated_names, Automaton, StateType};
+use crate::dfa::{Model, DFA};
+use ndarray::Array2;
+use std::collections::BTreeSet;
+use std::io::{Error, ErrorKind, Result};
+
+impl Automaton {
+	pub fn to_moore(&self) -> (DFA, Vec<char>) {
+		let automaton = self.determine().complete(None);
+		let n_states = automaton.states.len();
+		let n_inputs = automaton.inputs.len();
+		let mut output_matrix = Array2::zeros((n_states, 1));
+		for state in 0..n_states {
+			if automaton.is_final(state) {
+				output_matrix[(state, 0)] = 1;
+			}
+		}
+		let mut state_matrix = Array2::zeros((n_states, n_inputs));
+		for &(from, on, into) in &automaton.transitions {
+			state_matrix[(from, on)] = into;
+		}
+		(
+			DFA {
+				model: Model::Moore,
+				n_states,
+				n_inputs,
+				n_outputs: 2,
+				output_matrix,
+				state_matrix,
+			},
+			automaton.inputs,
+		)
+	}
+
+	pub fn from_moore(dfa: &DFA, inputs: &[char]) -> Result<Self> {
+		if dfa.model != Model::Moore || dfa.n_outputs > 2 {
+			return Err(Error::new(
+				ErrorKind::InvalidInput,
+				"acceptor should be Moore machine with outputs 0 and 1",
+			));
+		}
+		if inputs.len() != dfa.n_inputs {
+			return Err(Error::new(
+				ErrorKind::InvalidInput,
+				format!("expected {} inputs, found {}", dfa.n_inputs, inputs.len()),
+			));
+		}
+		let is_sink = |state: usize| {
+			state != 0
+				&& dfa.output_matrix[(state, 0)] == 0
+				&& dfa
+					.state_matrix
+					.row(state)
+					.iter()
+					.all(|&into| into == state)
+		};
+		let mut state_map = vec![usize::MAX; dfa.n_states];
+		let mut state_types = Vec::new();
+		for (state, mapped) in state_map.iter_mut().enumerate() {
+			if !is_sink(state) {
+				let mut state_type = StateType::default();
+				if state == 0 {
+					state_type |= StateType::INITIAL;
+				}
+				if dfa.output_matrix[(state, 0)] == 1 {
+					state_type |= StateType::FINAL;
+				}
+				*mapped = state_types.len();
+				state_types.push(state_type);
+			}
+		}
+		let mut transitions = BTreeSet::new();
+		for ((from, on), &into) in dfa.state_matrix.indexed_iter() {
+			if state_map[from] != usize::MAX && state_map[into] != usize::MAX {
+				transitions.insert((state_map[from], on, state_map[into]));
+			}
+		}
+		Ok(Self {
+			states: generated_names(state_types.len()),
+			state_types,
+			inputs: inputs.to_vec(),
+			transitions,
+			epsilon_transitions: BTreeSet::new(),
+		})
+	}
+}
@@ -0,0 +1,199 @@
+use super::{generated_names, Automaton, StateType};
+use crate::common::Symbols;
+use std::collections::BTreeSet;
+
+struct Parts {
+	state_types: Vec<StateType>,
+	inputs: Symbols<char>,
+	transitions: BTreeSet<(usize, usize, usize)>,
+	epsilon_transitions: BTreeSet<(usize, usize)>,
+}
+
+impl Parts {
+	fn new() -> Self {
+		Self {
+			state_types: Vec::new(),
+			inputs: Symbols::new(),
+			transitions: BTreeSet::new(),
+			epsilon_transitions: BTreeSet::new(),
+		}
+	}
+
+	fn append(&mut self, automaton: &Automaton) -> (Vec<usize>, Vec<usize>) {
+		let offset = self.state_types.len();
+		let mut initial_states = Vec::new();
+		let mut final_states = Vec::new();
+		for (id, &state_type) in automaton.state_types.iter().enumerate() {
+			if state_type & StateType::INITIAL {
+				initial_states.push(offset + id);
+			}
+			if state_type & StateType::FINAL {
+				final_states.push(offset + id);
+			}
+			self.state_types.push(state_type);
+		}
+		let inputs: Vec<usize> = automaton
+			.inputs
+			.iter()
+			.map(|&input| self.inputs.get_or_create_id(input))
+			.collect();
+		self.transitions.extend(
+			automaton
+				.transitions
+				.iter()
+				.map(|&(from, on, into)| (offset + from, inputs[on], offset + into)),
+		);
+		self.epsilon_transitions.extend(
+			automaton
+				.epsilon_transitions
+				.iter()
+				.map(|&(from, into)| (offset + from, offset + into)),
+		);
+		(initial_states, final_states)
+	}
+
+	fn add_state(&mut self, state_type: StateType) -> usize {
+		self.state_types.push(state_type);
+		self.state_types.len() - 1
+	}
+
+	fn connect(&mut self, from: &[usize], into: &[usize]) {
+		for &from in from {
+			self.epsilon_transitions
+				.extend(into.iter().map(|&into| (from, into)));
+		}
+	}
+
+	fn clear(&mut self, states: &[usize], state_type: StateType) {
+		for &state in states {
+			self.state_types[state] &= !state_type;
+		}
+	}
+
+	fn build(self) -> Automaton {
+		Automaton {
+			states: generated_names(self.state_types.len()),
+			state_types: self.state_types,
+			inputs: self.inputs.into_table(),
+			transitions: self.transitions,
+			epsilon_transitions: self.epsilon_transitions,
+		}
+	}
+}
+
+impl Automaton {
+	/// Accepts a word of `self` followed by a word of `other`.
+	///
+	/// ```
+	/// use fsm::Automaton;
+	///
+	/// let ab = Automaton::from_regexp("a")?.concat(&Automaton::from_regexp("b|c")?);
+	/// assert!(ab.accepts("ab") && ab.accepts("ac"));
+	/// assert!(!ab.accepts("a") && !ab.accepts("b") && !ab.accepts("abc"));
+	/// # Ok::<(), fsm::ParseRegExpError>(())
+	/// ```
+	pub fn concat(&self, other: &Self) -> Self {
+		let mut parts = Parts::new();
+		let (_, final_states) = parts.append(self);
+		let (initial_states, _) = parts.append(other);
+		parts.connect(&final_states, &initial_states);
+		parts.clear(&final_states, StateType::FINAL);
+		parts.clear(&initial_states, StateType::INITIAL);
+		parts.build()
+	}
+
+	pub(super) fn alternate(&self, other: &Self) -> Self {
+		let mut parts = Parts::new();
+		parts.append(self);
+		parts.append(other);
+		parts.build()
+	}
+
+	/// Accepts any number of words of `self`, including none.
+	///
+	/// ```
+	/// use fsm::Automaton;
+	///
+	/// let star = Automaton::from_regexp("ab")?.star();
+	/// assert!(star.accepts("") && star.accepts("ab") && star.accepts("abab"));
+	/// assert!(!star.accepts("a") && !star.accepts("aba"));
+	/// assert!(Automaton::from_regexp("∅")?.star().accepts(""));
+	/// # Ok::<(), fsm::ParseRegExpError>(())
+	/// ```
+	pub fn star(&self) -> Self {
+		let mut parts = Parts::new();
+		let (initial_states, final_states) = parts.append(self);
+		let start = parts.add_state(StateType::INITIAL | StateType::FINAL);
+		parts.connect(&[start], &initial_states);
+		parts.connect(&final_states, &[start]);
+		parts.clear(&initial_states, StateType::INITIAL);
+		parts.build()
+	}
+
+	/// Accepts one or more words of `self`.
+	///
+	/// ```
+	/// use fsm::Automaton;
+	///
+	/// let plus = Automaton::from_regexp("ab")?.plus();
+	/// assert!(plus.accepts("ab") && plus.accepts("abab"));
+	/// assert!(!plus.accepts("") && !plus.accepts("aba"));
+	/// # Ok::<(), fsm::ParseRegExpError>(())
+	/// ```
+	pub fn plus(&self) -> Self {
+		let mut parts = Parts::new();
+		let (initial_states, final_states) = parts.append(self);
+		parts.connect(&final_states, &initial_states);
+		parts.build()
+	}
+
+	/// Accepts the words of `self` and the empty word.
+	///
+	/// ```
+	/// use fsm::Automaton;
+	///
+	/// let optional = Automaton::from_regexp("ab")?.optional();
+	/// assert!(optional.accepts("") && optional.accepts("ab"));
+	/// assert!(!optional.accepts("abab"));
+	/// assert!(Automaton::from_regexp("∅")?.optional().accepts(""));
+	/// # Ok::<(), fsm::ParseRegExpError>(())
+	/// ```
+	pub fn optional(&self) -> Self {
+		let mut parts = Parts::new();
+		parts.append(self);
+		parts.add_state(StateType::INITIAL | StateType::FINAL);
+		parts.build()
+	}
+
+	/// Accepts the words of `self` read backwards.
+	///
+	/// ```
+	/// use fsm::Automaton;
+	///
+	/// let reverse = Automaton::from_regexp("ab*c")?.reverse();
+	/// assert!(reverse.accepts("ca") && reverse.accepts("cbba"));
+	/// assert!(!reverse.accepts("abc") && !reverse.accepts("cab"));
+	/// # Ok::<(), fsm::ParseRegExpError>(())
+	/// ```
+	pub fn reverse(&self) -> Self {
+		Self {
+			states: self.states.clone(),
+			state_types: self
+				.state_types
+				.iter()
+				.map(|state_type| state_type.reversed())
+				.collect(),
+			inputs: self.inputs.clone(),
+			transitions: self
+				.transitions
+				.iter()
+				.map(|&(from, on, into)| (into, on, from))
+				.collect(),
+			epsilon_transitions: self
+				.epsilon_transitions
+				.iter()
+				.map(|&(from, into)| (into, from))
+				.collect(),
+		}
+	}
+}
@@ -0,0 +1,162 @@
+use super::{Automaton, StateType};
+use crate::common::Symbols;
+use std::collections::BTreeSet;
+
+struct Operand<'a> {
+	automaton: &'a Automaton,
+	complete: bool,
+}
+
+impl<'a> Operand<'a> {
+	fn partial(automaton: &'a Automaton) -> Self {
+		Self {
+			automaton,
+			complete: false,
+		}
+	}
+
+	fn complete(automaton: &'a Automaton) -> Self {
+		Self {
+			automaton,
+			complete: true,
+		}
+	}
+
+	fn initial_states(&self) -> Vec<Option<usize>> {
+		let states: Vec<Option<usize>> = self
+			.automaton
+			.initial_states()
+			.into_iter()
+			.map(Some)
+			.collect();
+		if states.is_empty() && self.complete {
+			vec![None]
+		} else {
+			states
+		}
+	}
+
+	fn next_states(&self, from: Option<usize>, on: Option<usize>) -> Vec<Option<usize>> {
+		let states: Vec<Option<usize>> = match (from, on) {
+			(Some(from), Some(on)) => self
+				.automaton
+				.transitions
+				.range((from, on, 0)..(from, on + 1, 0))
+				.map(|&(_, _, into)| Some(into))
+				.collect(),
+			_ => Vec::new(),
+		};
+		if states.is_empty() && self.complete {
+			vec![None]
+		} else {
+			states
+		}
+	}
+
+	fn is_final(&self, state: Option<usize>) -> bool {
+		state.is_some_and(|state| self.automaton.is_final(state))
+	}
+
+	fn state_name(&self, state: Option<usize>) -> &str {
+		state.map_or("∅", |state| &self.automaton.states[state])
+	}
+}
+
+fn product<F: Fn(bool, bool) -> bool>(lhs: Operand, rhs: Operand, accepts: F) -> Automaton {
+	let mut inputs = Symbols::new();
+	for &input in lhs.automaton.inputs.iter().chain(&rhs.automaton.inputs) {
+		inputs.get_or_create_id(input);
+	}
+	let inputs = inputs.into_table();
+	let input_ids: Vec<(Option<usize>, Option<usize>)> = inputs
+		.iter()
+		.map(|&c| (lhs.automaton.input_id(c), rhs.automaton.input_id(c)))
+		.collect();
+	let mut states = Symbols::new();
+	let mut initial_states = BTreeSet::new();
+	for &lhs_state in &lhs.initial_states() {
+		for &rhs_state in &rhs.initial_states() {
+			initial_states.insert(states.get_or_create_id((lhs_state, rhs_state)));
+		}
+	}
+	let mut transitions = BTreeSet::new();
+	let mut from = 0;
+	while from < states.len() {
+		let (lhs_from, rhs_from) = *states.get_name(from);
+		for (on, &(lhs_on, rhs_on)) in input_ids.iter().enumerate() {
+			for &lhs_into in &lhs.next_states(lhs_from, lhs_on) {
+				for &rhs_into in &rhs.next_states(rhs_from, rhs_on) {
+					if lhs_into.is_some() || rhs_into.is_some() {
+						let into = states.get_or_create_id((lhs_into, rhs_into));
+						transitions.insert((from, on, into));
+					}
+				}
+			}
+		}
+		from += 1;
+	}
+	let states = states.into_table();
+	Automaton {
+		state_types: states
+			.iter()
+			.enumerate()
+			.map(|(id, &(lhs_state, rhs_state))| {
+				let mut state_type = StateType::default();
+				if initial_states.contains(&id) {
+					state_type |= StateType::INITIAL;
+				}
+				if accepts(lhs.is_final(lhs_state), rhs.is_final(rhs_state)) {
+					state_type |= StateType::FINAL;
+				}
+				state_type
+			})
+			.collect(),
+		states: states
+			.into_iter()
+			.map(|(lhs_state, rhs_state)| {
+				format!(
+					"({},{})",
+					lhs.state_name(lhs_state),
+					rhs.state_name(rhs_state)
+				)
+			})
+			.collect(),
+		inputs,
+		transitions,
+		epsilon_transitions: BTreeSet::new(),
+	}
+}
+
+impl Automaton {
+	pub fn intersect(&self, other: &Self) -> Self {
+		product(
+			Operand::partial(&self.remove_epsilons()),
+			Operand::partial(&other.remove_epsilons()),
+			|lhs, rhs| lhs && rhs,
+		)
+	}
+
+	pub fn union(&self, other: &Self) -> Self {
+		product(
+			Operand::complete(&self.remove_epsilons()),
+			Operand::complete(&other.remove_epsilons()),
+			|lhs, rhs| lhs || rhs,
+		)
+	}
+
+	pub fn difference(&self, other: &Self) -> Self {
+		product(
+			Operand::partial(&self.remove_epsilons()),
+			Operand::complete(&other.determine()),
+			|lhs, rhs| lhs && !rhs,
+		)
+	}
+
+	pub fn symmetric_difference(&self, other: &Self) -> Self {
+		product(
+			Operand::complete(&self.determine()),
+			Operand::complete(&other.determine()),
+			|lhs, rhs| lhs != rhs,
+		)
+	}
+}
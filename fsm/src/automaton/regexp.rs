@@ -1,152 +1,164 @@
-use super::{Automaton, StateType};
+use super::{generated_names, Automaton, StateType, EPSILON};
 use crate::common::Symbols;
+use crate::regexp::{ParseRegExpError, RegExp};
 use std::collections::BTreeSet;
-use std::fmt;
 
 struct Builder {
 	n_states: usize,
 	inputs: Symbols<char>,
 	transitions: BTreeSet<(usize, usize, usize)>,
-	expression: Vec<ExprPart>,
-}
-
-enum ExprPart {
-	StartGroup(usize),
-	Alternative,
-	MachinePart(MachinePart),
 }
 
 struct MachinePart {
+	first_id: usize,
 	is_final: bool,
 	final_ids: BTreeSet<usize>,
 	transitions: BTreeSet<(usize, usize)>,
 }
 
-pub struct ParseRegExpError {
-	position: usize,
-	kind: RegExpErrorKind,
-}
-
-enum RegExpErrorKind {
-	MissingPostfixUnaryOpArg(char),
-	UnmatchedParenthesis,
-	InvalidEscapeSequence,
-}
-
 impl Automaton {
 	pub fn from_regexp(regexp: &str) -> Result<Self, ParseRegExpError> {
-		let mut builder = Builder::new();
-		let mut position = 0;
-		let mut escaped = false;
-		for c in regexp.chars() {
-			position += 1;
-			if escaped {
-				escaped = false;
-				builder.push_char(c);
-			} else {
-				match c {
-					'(' => builder.start_group(position),
-					')' => builder.end_group().map_err(|()| ParseRegExpError {
-						position,
-						kind: RegExpErrorKind::UnmatchedParenthesis,
-					})?,
-					'*' => builder.repeat_top(false).map_err(|()| ParseRegExpError {
-						position,
-						kind: RegExpErrorKind::MissingPostfixUnaryOpArg('*'),
-					})?,
-					'+' => builder.repeat_top(true).map_err(|()| ParseRegExpError {
-						position,
-						kind: RegExpErrorKind::MissingPostfixUnaryOpArg('+'),
-					})?,
-					'\\' => escaped = true,
-					'|' => builder.push_alternative(),
-					_ => builder.push_char(c),
-				}
-			}
-		}
-		if escaped {
-			return Err(ParseRegExpError {
-				position,
-				kind: RegExpErrorKind::InvalidEscapeSequence,
-			});
-		}
-		builder.build()
+		Self::from_regexp_with_alphabet(regexp, &[])
 	}
-}
 
-impl Builder {
-	fn new() -> Self {
-		Self {
+	pub fn from_regexp_with_alphabet(
+		regexp: &str,
+		alphabet: &[char],
+	) -> Result<Self, ParseRegExpError> {
+		Ok(Self::from_parsed_regexp(&RegExp::parse(regexp)?, alphabet))
+	}
+
+	pub fn from_parsed_regexp(regexp: &RegExp, alphabet: &[char]) -> Self {
+		let inputs = regexp_inputs(regexp, alphabet);
+		if regexp.is_extended() {
+			return Self::compose_regexp(regexp, &inputs.into_table());
+		}
+		let mut builder = Builder {
 			n_states: 0,
-			inputs: Symbols::new(),
+			inputs,
 			transitions: BTreeSet::new(),
-			expression: Vec::new(),
+		};
+		let start = builder.push(regexp);
+		builder.build(start)
+	}
+}
+
+pub(super) fn regexp_inputs(regexp: &RegExp, alphabet: &[char]) -> Symbols<char> {
+	let mut inputs = Symbols::new();
+	for c in alphabet.iter().copied().chain(regexp.alphabet()) {
+		if c != EPSILON {
+			inputs.get_or_create_id(c);
 		}
 	}
+	inputs
+}
 
-	fn build(mut self) -> Result<Automaton, ParseRegExpError> {
-		if let Some(ExprPart::StartGroup(position)) = self
-			.expression
-			.iter()
-			.find(|&part| ExprPart::is_start_group(part))
-		{
-			return Err(ParseRegExpError {
-				position: *position,
-				kind: RegExpErrorKind::UnmatchedParenthesis,
-			});
-		}
-		let start = MachinePart::reduce(&mut self.expression, &mut self.transitions);
-		Ok(Automaton {
-			states: self
-				.finalize(start)
-				.iter()
-				.enumerate()
-				.map(|(state_id, state_type)| format!("S{}{}", state_id, state_type.name()))
-				.collect(),
+impl Builder {
+	fn build(mut self, start: MachinePart) -> Automaton {
+		Automaton {
+			state_types: self.finalize(start),
+			states: generated_names(self.n_states),
 			inputs: self.inputs.into_table(),
 			transitions: self.transitions,
-		})
-	}
-
-	fn start_group(&mut self, position: usize) {
-		self.expression.push(ExprPart::StartGroup(position));
-	}
-
-	fn end_group(&mut self) -> Result<(), ()> {
-		let start_index = match self.expression.iter().rposition(ExprPart::is_start_group) {
-			Some(index) => index,
-			None => return Err(()),
-		};
-		let machine_part = MachinePart::reduce(
-			self.expression.split_at_mut(start_index + 1).1,
-			&mut self.transitions,
-		);
-		self.expression.truncate(start_index);
-		self.expression.push(ExprPart::MachinePart(machine_part));
-		Ok(())
+			epsilon_transitions: BTreeSet::new(),
+		}
 	}
 
-	fn push_alternative(&mut self) {
-		self.expression.push(ExprPart::Alternative);
+	fn push(&mut self, regexp: &RegExp) -> MachinePart {
+		let first_id = self.n_states;
+		match regexp {
+			RegExp::Empty => MachinePart::new(first_id, false),
+			RegExp::Epsilon => MachinePart::new(first_id, true),
+			RegExp::Concat(parts) => {
+				let mut concatenated = MachinePart::new(first_id, true);
+				for part in parts {
+					let part = self.push(part);
+					concatenated.concat(&part, &mut self.transitions);
+				}
+				concatenated
+			}
+			RegExp::Alternation(variants) => {
+				let variants: Vec<MachinePart> =
+					variants.iter().map(|variant| self.push(variant)).collect();
+				let mut merged = MachinePart::merge(variants);
+				merged.first_id = first_id;
+				merged
+			}
+			RegExp::Star(inner) => self.push_repeated(inner, 0, None),
+			RegExp::Plus(inner) => self.push_repeated(inner, 1, None),
+			RegExp::Optional(inner) => self.push_repeated(inner, 0, Some(1)),
+			RegExp::Repeat(..) if regexp.is_empty_repeat() => self.push(&RegExp::Empty),
+			RegExp::Repeat(inner, min, max) => self.push_repeated(inner, *min, *max),
+			RegExp::Intersection(_) | RegExp::Complement(_) => unreachable!(),
+			class => {
+				let chars = class.matched_chars(self.inputs.table()).unwrap();
+				self.push_class(&chars)
+			}
+		}
 	}
 
-	fn push_char(&mut self, c: char) {
-		self.expression.push(ExprPart::MachinePart(MachinePart {
-			is_final: false,
-			final_ids: BTreeSet::from([self.n_states]),
-			transitions: BTreeSet::from([(self.inputs.get_or_create_id(c), self.n_states)]),
-		}));
+	fn push_class(&mut self, chars: &[char]) -> MachinePart {
+		let transitions = chars
+			.iter()
+			.map(|&c| (self.inputs.get_or_create_id(c), self.n_states))
+			.collect();
 		self.n_states += 1;
+		MachinePart {
+			first_id: self.n_states - 1,
+			is_final: false,
+			final_ids: BTreeSet::from([self.n_states - 1]),
+			transitions,
+		}
 	}
 
-	fn repeat_top(&mut self, at_least_once: bool) -> Result<(), ()> {
-		let machine_part = match self.expression.last_mut() {
-			Some(ExprPart::MachinePart(part)) => part,
-			_ => return Err(()),
-		};
-		machine_part.connect(machine_part, &mut self.transitions);
-		machine_part.is_final |= !at_least_once;
-		Ok(())
+	fn push_repeated(&mut self, inner: &RegExp, min: usize, max: Option<usize>) -> MachinePart {
+		if max == Some(0) {
+			return MachinePart::new(self.n_states, true);
+		}
+		let machine_part = self.push(inner);
+		let first_id = machine_part.first_id;
+		let count = max.unwrap_or_else(|| min.max(1));
+		let end_id = self.n_states;
+		let mut copies = vec![machine_part];
+		for _ in 1..count {
+			copies.push(self.copy_part(&copies[0], end_id));
+		}
+		for copy in &mut copies[min..] {
+			copy.is_final = true;
+		}
+		if max.is_none() {
+			let last = copies.last_mut().unwrap();
+			last.connect(last, &mut self.transitions);
+		}
+		let mut copies = copies.into_iter();
+		let mut repeated = copies.next().unwrap();
+		for copy in copies {
+			repeated.concat(&copy, &mut self.transitions);
+		}
+		repeated.first_id = first_id;
+		repeated
+	}
+
+	fn copy_part(&mut self, part: &MachinePart, end_id: usize) -> MachinePart {
+		let first_id = self.n_states;
+		let offset = first_id - part.first_id;
+		let copied: Vec<(usize, usize, usize)> = self
+			.transitions
+			.range((part.first_id, 0, 0)..(end_id, 0, 0))
+			.map(|&(from, on, into)| (from + offset, on, into + offset))
+			.collect();
+		self.transitions.extend(copied);
+		self.n_states += end_id - part.first_id;
+		MachinePart {
+			first_id,
+			is_final: part.is_final,
+			final_ids: part.final_ids.iter().map(|&id| id + offset).collect(),
+			transitions: part
+				.transitions
+				.iter()
+				.map(|&(on, into)| (on, into + offset))
+				.collect(),
+		}
 	}
 
 	fn finalize(&mut self, start: MachinePart) -> Vec<StateType> {
@@ -170,37 +182,19 @@ impl Builder {
 	}
 }
 
-impl ExprPart {
-	fn is_start_group(&self) -> bool {
-		matches!(self, Self::StartGroup(_))
-	}
-
-	fn is_alternative(&self) -> bool {
-		matches!(self, Self::Alternative)
-	}
-}
-
 impl MachinePart {
-	fn reduce(
-		variants: &mut [ExprPart],
-		transitions: &mut BTreeSet<(usize, usize, usize)>,
-	) -> Self {
-		Self::merge(variants.split_mut(ExprPart::is_alternative).map(|variant| {
-			let mut parts = variant.iter_mut();
-			let mut variant = Self {
-				is_final: true,
-				final_ids: BTreeSet::new(),
-				transitions: BTreeSet::new(),
-			};
-			while let Some(ExprPart::MachinePart(part)) = parts.next() {
-				variant.concat(part, transitions);
-			}
-			variant
-		}))
+	fn new(first_id: usize, is_final: bool) -> Self {
+		Self {
+			first_id,
+			is_final,
+			final_ids: BTreeSet::new(),
+			transitions: BTreeSet::new(),
+		}
 	}
 
 	fn merge<I: IntoIterator<Item = Self>>(parts: I) -> Self {
 		let mut merged = Self {
+			first_id: 0,
 			is_final: false,
 			final_ids: BTreeSet::new(),
 			transitions: BTreeSet::new(),
@@ -235,21 +229,3 @@ impl MachinePart {
 		}
 	}
 }
-
-impl fmt::Display for ParseRegExpError {
-	fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-		write!(formatter, "{}: {}", self.position, self.kind)
-	}
-}
-
-impl fmt::Display for RegExpErrorKind {
-	fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-		match self {
-			Self::MissingPostfixUnaryOpArg(op) => {
-				write!(formatter, "postfix operator '{}' takes 1 argument", op)
-			}
-			Self::UnmatchedParenthesis => formatter.write_str("unmatched parenthesis"),
-			Self::InvalidEscapeSequence => formatter.write_str("invalid escape sequence"),
-		}
-	}
-}
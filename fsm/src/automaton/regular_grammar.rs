@@ -1,11 +1,12 @@
-use super::{Automaton, StateType};
-use crate::common::{parse_array0, Symbols};
+use super::{Automaton, StateType, EPSILON};
+use crate::common::{parse_array0, LoadError, Position, Symbols, TokenReader};
 use std::collections::BTreeSet;
-use std::io::{BufRead, Error, ErrorKind, Result};
+use std::fmt;
+use std::io::{self, BufRead, Error, ErrorKind, Write};
 use std::str::FromStr;
 
-#[derive(Clone, Copy)]
-enum GrammarType {
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum GrammarType {
 	RightRegular,
 	LeftRegular,
 }
@@ -26,24 +27,56 @@ fn set_final_rule(state_type: &mut StateType, grammar_type: GrammarType) {
 	};
 }
 
+fn is_valid_nonterminal(name: &str) -> bool {
+	!name.is_empty() && !name.contains(|c: char| c.is_whitespace() || c == '|')
+}
+
+fn create_nonterminal(used: &mut BTreeSet<String>, prefix: &str) -> String {
+	let name = (0..)
+		.map(|id| format!("{}{}", prefix, id))
+		.find(|name| !used.contains(name))
+		.unwrap();
+	used.insert(name.clone());
+	name
+}
+
 impl Automaton {
-	pub fn load_from_regular_grammar<R: BufRead>(reader: &mut R) -> Result<Self> {
-		let mut line = String::new();
-		let grammar_type = parse_array0(reader, &mut line)?;
+	pub fn load_from_regular_grammar<R: BufRead>(reader: &mut R) -> Result<Self, LoadError> {
+		Self::parse_regular_grammar(reader).map(|(automaton, _)| automaton)
+	}
+
+	pub fn convert_regular_grammar<R: BufRead, W: Write>(
+		reader: &mut R,
+		writer: &mut W,
+	) -> Result<(), LoadError> {
+		let (automaton, grammar_type) = Self::parse_regular_grammar(reader)?;
+		let nonterminals: Vec<&str> = automaton.states.iter().map(String::as_str).collect();
+		Ok(automaton.write_regular_grammar(writer, grammar_type.opposite(), &nonterminals)?)
+	}
+
+	fn parse_regular_grammar<R: BufRead>(reader: &mut R) -> Result<(Self, GrammarType), LoadError> {
+		let mut reader = TokenReader::without_comments(reader);
+		let grammar_type = parse_array0(&mut reader)?;
 		let mut states = Symbols::new();
 		let mut inputs = Symbols::new();
 		let mut transitions = BTreeSet::new();
 		let mut final_rules = BTreeSet::new();
-		for _ in 0..parse_array0(reader, &mut line)? {
-			reader.read_line(&mut line)?;
-			let mut tokens = line.split_whitespace();
-			let first_state = states.get_or_create_id(
-				tokens
-					.next()
-					.ok_or_else(|| Error::new(ErrorKind::InvalidData, "no state name specified"))?
-					.to_string(),
-			);
-			for token in tokens.collect::<Vec<&str>>().join("").split('|') {
+		for _ in 0..parse_array0(&mut reader)? {
+			let (_, name) = reader
+				.next_token()?
+				.ok_or_else(|| LoadError::MissingStateName(reader.position()))?;
+			let first_state = states.get_or_create_id(name);
+			let mut rule = String::new();
+			while let Some((position, token)) = reader.next_token_on_line() {
+				if let Some(index) = token.chars().position(|c| c == EPSILON) {
+					return Err(LoadError::ReservedEpsilon(Position {
+						column: position.column + index,
+						..position
+					}));
+				}
+				rule.push_str(&token);
+			}
+			for token in rule.split('|') {
 				let mut token = token.chars();
 				match match grammar_type {
 					GrammarType::RightRegular => token.next(),
@@ -62,7 +95,6 @@ impl Automaton {
 					}
 				}
 			}
-			line.clear();
 		}
 		let mut state_types = vec![StateType::default(); states.len()];
 		if let Some(start_rule) = state_types.get_mut(0) {
@@ -74,22 +106,167 @@ impl Automaton {
 		for rule in final_rules {
 			set_final_rule(&mut state_types[rule], grammar_type);
 		}
-		let mut automaton = Self {
-			states: states.into_table(),
+		let mut states = states.into_table();
+		if let Some(final_state) = states.iter().position(String::is_empty) {
+			states[final_state] = std::iter::once("F".to_string())
+				.chain((0..).map(|id| format!("F{}", id)))
+				.find(|name| !states.contains(name))
+				.unwrap();
+		}
+		let automaton = Self {
+			states,
+			state_types,
 			inputs: inputs.into_table(),
 			transitions,
+			epsilon_transitions: BTreeSet::new(),
+		};
+		Ok((automaton, grammar_type))
+	}
+}
+
+impl Automaton {
+	pub fn store_as_regular_grammar<W: Write>(
+		&self,
+		writer: &mut W,
+		grammar_type: GrammarType,
+	) -> io::Result<()> {
+		let nonterminals: Vec<&str> = self.states.iter().map(String::as_str).collect();
+		self.write_regular_grammar(writer, grammar_type, &nonterminals)
+	}
+
+	fn write_regular_grammar<W: Write>(
+		&self,
+		writer: &mut W,
+		grammar_type: GrammarType,
+		names: &[&str],
+	) -> io::Result<()> {
+		if let Some(input) = self
+			.inputs
+			.iter()
+			.find(|&&input| input.is_whitespace() || input == '|')
+		{
+			return Err(Error::new(
+				ErrorKind::InvalidInput,
+				format!("input {:?} cannot be used as terminal", input),
+			));
+		}
+		let automaton = match grammar_type {
+			GrammarType::RightRegular => self.remove_epsilons(),
+			GrammarType::LeftRegular => self.remove_epsilons().reverse(),
 		};
-		for (state_type, state) in state_types.into_iter().zip(&mut automaton.states) {
-			state.push(state_type.name());
+		let state_types = &automaton.state_types;
+		let mut used: BTreeSet<String> = names
+			.iter()
+			.filter(|name| is_valid_nonterminal(name))
+			.map(|name| name.to_string())
+			.collect();
+		let nonterminals: Vec<String> = names
+			.iter()
+			.map(|name| {
+				if is_valid_nonterminal(name) {
+					name.to_string()
+				} else {
+					create_nonterminal(&mut used, "N")
+				}
+			})
+			.collect();
+		let is_terminal_only: Vec<bool> = state_types
+			.iter()
+			.enumerate()
+			.map(|(state, &state_type)| {
+				state_type & StateType::FINAL
+					&& !(state_type & StateType::INITIAL)
+					&& automaton
+						.transitions
+						.range((state, 0, 0)..(state + 1, 0, 0))
+						.next()
+						.is_none()
+			})
+			.collect();
+		let mut rules: Vec<(String, Vec<String>)> = Vec::new();
+		for (state, nonterminal) in nonterminals.iter().enumerate() {
+			let mut alternatives = Vec::new();
+			for &(_, on, into) in automaton
+				.transitions
+				.range((state, 0, 0)..(state + 1, 0, 0))
+			{
+				let terminal = automaton.inputs[on];
+				let alternative = match (is_terminal_only[into], grammar_type) {
+					(true, _) => terminal.to_string(),
+					(false, GrammarType::RightRegular) => {
+						format!("{}{}", terminal, nonterminals[into])
+					}
+					(false, GrammarType::LeftRegular) => {
+						format!("{}{}", nonterminals[into], terminal)
+					}
+				};
+				if !alternatives.contains(&alternative) {
+					alternatives.push(alternative);
+				}
+			}
+			if state_types[state] & StateType::FINAL {
+				alternatives.push(String::new());
+			}
+			rules.push((nonterminal.clone(), alternatives));
 		}
-		Ok(automaton)
+		let initial_states: Vec<usize> = (0..automaton.states.len())
+			.filter(|&state| state_types[state] & StateType::INITIAL)
+			.collect();
+		let start = match initial_states.as_slice() {
+			[] => None,
+			&[start] => Some(rules[start].clone()),
+			_ => {
+				let mut alternatives = Vec::new();
+				for &state in &initial_states {
+					for alternative in &rules[state].1 {
+						if !alternatives.contains(alternative) {
+							alternatives.push(alternative.clone());
+						}
+					}
+				}
+				Some((create_nonterminal(&mut used, "S"), alternatives))
+			}
+		};
+		let rules: Vec<&(String, Vec<String>)> = match &start {
+			Some(start) if !start.1.is_empty() => std::iter::once(start)
+				.chain(rules.iter().enumerate().filter_map(|(state, rule)| {
+					(rule.0 != start.0 && !rule.1.is_empty() && !is_terminal_only[state])
+						.then_some(rule)
+				}))
+				.collect(),
+			_ => Vec::new(),
+		};
+		writeln!(writer, "{}", grammar_type)?;
+		writeln!(writer, "{}", rules.len())?;
+		for (nonterminal, alternatives) in rules {
+			writeln!(writer, "{}\t{}", nonterminal, alternatives.join(" | "))?;
+		}
+		Ok(())
+	}
+}
+
+impl GrammarType {
+	pub fn opposite(self) -> Self {
+		match self {
+			GrammarType::RightRegular => GrammarType::LeftRegular,
+			GrammarType::LeftRegular => GrammarType::RightRegular,
+		}
+	}
+}
+
+impl fmt::Display for GrammarType {
+	fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		formatter.write_str(match self {
+			GrammarType::RightRegular => "R",
+			GrammarType::LeftRegular => "L",
+		})
 	}
 }
 
 impl FromStr for GrammarType {
 	type Err = Error;
 
-	fn from_str(s: &str) -> Result<Self> {
+	fn from_str(s: &str) -> io::Result<Self> {
 		match s {
 			"R" => Ok(GrammarType::RightRegular),
 			"L" => Ok(GrammarType::LeftRegular),
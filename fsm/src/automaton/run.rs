@@ -0,0 +1,60 @@
+use super::{Automaton, StateType};
+use std::collections::BTreeSet;
+
+impl Automaton {
+	pub fn state_name(&self, state: usize) -> &str {
+		&self.states[state]
+	}
+
+	pub fn is_initial(&self, state: usize) -> bool {
+		self.state_types[state] & StateType::INITIAL
+	}
+
+	pub fn is_final(&self, state: usize) -> bool {
+		self.state_types[state] & StateType::FINAL
+	}
+
+	pub fn run(&self, word: &str) -> Vec<BTreeSet<usize>> {
+		let mut active = self.initial_closure();
+		let mut trace = Vec::with_capacity(word.len() + 1);
+		for c in word.chars() {
+			let next = match self.input_id(c) {
+				Some(on) => self.step(&active, on),
+				None => BTreeSet::new(),
+			};
+			trace.push(active);
+			active = next;
+		}
+		trace.push(active);
+		trace
+	}
+
+	pub fn accepts(&self, word: &str) -> bool {
+		self.is_accepting(self.run(word).last().unwrap())
+	}
+
+	pub(super) fn is_accepting(&self, states: &BTreeSet<usize>) -> bool {
+		states.iter().any(|&state| self.is_final(state))
+	}
+
+	pub(super) fn initial_closure(&self) -> BTreeSet<usize> {
+		let mut states = self.initial_states();
+		self.epsilon_closure(&mut states);
+		states
+	}
+
+	pub(super) fn input_id(&self, c: char) -> Option<usize> {
+		self.inputs.iter().position(|&input| input == c)
+	}
+
+	pub(super) fn step(&self, states: &BTreeSet<usize>, on: usize) -> BTreeSet<usize> {
+		let mut next = BTreeSet::new();
+		for &from in states {
+			for &(_, _, into) in self.transitions.range((from, on, 0)..(from, on + 1, 0)) {
+				next.insert(into);
+			}
+		}
+		self.epsilon_closure(&mut next);
+		next
+	}
+}
@@ -1,52 +1,260 @@
-use super::Automaton;
-use crate::common::{parse_array0, Symbols};
-use std::collections::BTreeSet;
-use std::io::{BufRead, Error, ErrorKind, Result, Write};
+use super::{Automaton, StateType, EPSILON};
+use crate::common::{parse_array0, LoadError, Position, Symbols, TokenReader};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+
+fn read_declaration<R: BufRead>(
+	reader: &mut TokenReader<R>,
+) -> Result<(Position, String), LoadError> {
+	reader
+		.next_token()?
+		.ok_or_else(|| LoadError::MissingDeclarations(reader.position()))
+}
+
+fn read_input_declaration<R: BufRead>(
+	reader: &mut TokenReader<R>,
+) -> Result<(Position, char), LoadError> {
+	loop {
+		let (position, token) = reader
+			.next_raw_token()?
+			.ok_or_else(|| LoadError::MissingDeclarations(reader.position()))?;
+		if token.starts_with('#') && !(token == "#" && reader.at_line_end()) {
+			while reader.next_raw_token_on_line().is_some() {}
+			continue;
+		}
+		let on = parse_input(position, &token)?;
+		if let Some((position, token)) = reader.next_token_on_line() {
+			return Err(LoadError::UnexpectedToken(position, token));
+		}
+		return Ok((position, on));
+	}
+}
+
+fn is_header<R: BufRead>(
+	reader: &TokenReader<R>,
+	token: &Option<(Position, String)>,
+	keyword: &str,
+) -> bool {
+	matches!(token, Some((_, token)) if token == keyword) && reader.at_line_end()
+}
+
+fn parse_state_flags<R: BufRead>(reader: &mut TokenReader<R>) -> Result<StateType, LoadError> {
+	let mut state_type = StateType::default();
+	while let Some((position, flag)) = reader.next_token_on_line() {
+		state_type |= match flag.as_str() {
+			"initial" => StateType::INITIAL,
+			"final" => StateType::FINAL,
+			_ => return Err(LoadError::UnknownStateFlag(position, flag)),
+		};
+	}
+	Ok(state_type)
+}
+
+fn parse_input(position: Position, token: &str) -> Result<char, LoadError> {
+	let on: Vec<char> = token.chars().take(2).collect();
+	if on.len() != 1 {
+		return Err(LoadError::InvalidInput(position, token.to_string()));
+	}
+	Ok(on[0])
+}
+
+fn get_declared_id<T: Display + Ord>(
+	symbols: &Symbols<T>,
+	position: Position,
+	name: &T,
+	what: &'static str,
+) -> Result<usize, LoadError> {
+	symbols
+		.get_id(name)
+		.copied()
+		.ok_or_else(|| LoadError::UndeclaredSymbol(position, what, name.to_string()))
+}
 
 impl Automaton {
-	pub fn load_from_simple_text<R: BufRead>(reader: &mut R) -> Result<Self> {
-		let mut line = String::new();
-		let mut states = Symbols::with_capacity(parse_array0(reader, &mut line)?);
-		let mut inputs = Symbols::with_capacity(parse_array0(reader, &mut line)?);
+	pub fn load_from_simple_text<R: BufRead>(reader: &mut R) -> Result<Self, LoadError> {
+		Self::parse_simple_text(&mut TokenReader::new(reader), false)
+	}
+
+	pub fn load_from_suffixed_simple_text<R: BufRead>(reader: &mut R) -> Result<Self, LoadError> {
+		Self::parse_simple_text(&mut TokenReader::new(reader), true)
+	}
+
+	pub fn load<R: BufRead>(reader: &mut R, suffixed: bool) -> Result<Self, LoadError> {
+		Self::parse_simple_text(&mut TokenReader::new(reader), suffixed)
+	}
+
+	fn parse_simple_text<R: BufRead>(
+		reader: &mut TokenReader<R>,
+		suffixed: bool,
+	) -> Result<Self, LoadError> {
+		let n_states = parse_array0(reader)?;
+		let n_inputs = parse_array0(reader)?;
+		let mut states = Symbols::with_capacity(n_states);
+		let mut inputs = Symbols::with_capacity(n_inputs);
+		let mut state_types = Vec::new();
+		let mut token = reader.next_token()?;
+		let has_states = is_header(reader, &token, "states");
+		if has_states {
+			for _ in 0..n_states {
+				let (position, name) = read_declaration(reader)?;
+				if states.get_id(&name).is_some() {
+					return Err(LoadError::DuplicateState(position, name));
+				}
+				states.get_or_create_id(name);
+				state_types.push(parse_state_flags(reader)?);
+			}
+			token = reader.next_token()?;
+		}
+		let has_inputs = is_header(reader, &token, "inputs");
+		if has_inputs {
+			for _ in 0..n_inputs {
+				let (position, on) = read_input_declaration(reader)?;
+				if on == EPSILON || inputs.get_id(&on).is_some() {
+					return Err(LoadError::InvalidInputDeclaration(position, on));
+				}
+				inputs.get_or_create_id(on);
+			}
+			token = reader.next_token()?;
+		}
 		let mut transitions = BTreeSet::new();
-		while reader.read_line(&mut line)? > 0 {
-			let tokens: Vec<&str> = line.split_whitespace().take(4).collect();
+		let mut epsilon_transitions = BTreeSet::new();
+		while let Some(first) = token {
+			let mut tokens = vec![first];
+			tokens.extend(reader.next_raw_token_on_line());
+			while let Some(token) = reader.next_token_on_line() {
+				tokens.push(token);
+			}
 			if tokens.len() != 3 {
-				return Err(Error::new(
-					ErrorKind::InvalidData,
-					"transition definition should contain 3 arguments",
-				));
+				return Err(LoadError::InvalidTransition(tokens[0].0, tokens.len()));
 			}
-			let on: Vec<char> = tokens[1].chars().take(2).collect();
-			if on.len() != 1 {
-				return Err(Error::new(
-					ErrorKind::InvalidData,
-					"input should be single character",
-				));
+			let on = parse_input(tokens[1].0, &tokens[1].1)?;
+			let (from, into) = if has_states {
+				(
+					get_declared_id(&states, tokens[0].0, &tokens[0].1, "state")?,
+					get_declared_id(&states, tokens[2].0, &tokens[2].1, "state")?,
+				)
+			} else {
+				(
+					states.get_or_create_id(tokens[0].1.clone()),
+					states.get_or_create_id(tokens[2].1.clone()),
+				)
+			};
+			if on == EPSILON {
+				epsilon_transitions.insert((from, into));
+			} else if has_inputs {
+				let on = get_declared_id(&inputs, tokens[1].0, &on, "input")?;
+				transitions.insert((from, on, into));
+			} else {
+				transitions.insert((from, inputs.get_or_create_id(on), into));
 			}
-			let from = states.get_or_create_id(tokens[0].to_string());
-			let on = inputs.get_or_create_id(on[0]);
-			let into = states.get_or_create_id(tokens[2].to_string());
-			transitions.insert((from, on, into));
-			line.clear();
+			if states.len() > n_states || inputs.len() > n_inputs {
+				return Err(LoadError::TooManySymbols(tokens[0].0));
+			}
+			token = reader.next_token()?;
+		}
+		let states = states.into_table();
+		if !has_states {
+			state_types = if suffixed {
+				states
+					.iter()
+					.map(|name| StateType::from_last_char(name))
+					.collect()
+			} else {
+				vec![StateType::default(); states.len()]
+			};
 		}
 		Ok(Self {
-			states: states.into_table(),
+			states,
+			state_types,
 			inputs: inputs.into_table(),
 			transitions,
+			epsilon_transitions,
 		})
 	}
 
-	pub fn store_as_simple_text<W: Write>(&self, writer: &mut W) -> Result<()> {
+	pub fn store<W: Write>(&self, writer: &mut W, suffixed: bool) -> io::Result<()> {
+		if suffixed {
+			self.store_as_suffixed_simple_text(writer)
+		} else {
+			self.store_as_simple_text(writer)
+		}
+	}
+
+	pub fn store_as_simple_text<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+		self.check_state_names()?;
+		writeln!(writer, "{}", self.states.len())?;
+		writeln!(writer, "{}", self.inputs.len())?;
+		writeln!(writer, "states")?;
+		for (state, name) in self.states.iter().enumerate() {
+			write!(writer, "{}", name)?;
+			if self.is_initial(state) {
+				write!(writer, " initial")?;
+			}
+			if self.is_final(state) {
+				write!(writer, " final")?;
+			}
+			writeln!(writer)?;
+		}
+		writeln!(writer, "inputs")?;
+		for input in &self.inputs {
+			writeln!(writer, "{}", input)?;
+		}
+		self.write_transitions(writer, &self.states)
+	}
+
+	pub fn store_as_suffixed_simple_text<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+		self.check_state_names()?;
+		let names: Vec<String> = self
+			.states
+			.iter()
+			.zip(&self.state_types)
+			.map(|(name, &state_type)| {
+				if name.ends_with(state_type.name()) {
+					name.clone()
+				} else {
+					format!("{}{}", name, state_type.name())
+				}
+			})
+			.collect();
+		let mut written = BTreeMap::new();
+		for (state, name) in names.iter().enumerate() {
+			if let Some(other) = written.insert(name, state) {
+				return Err(io::Error::new(
+					io::ErrorKind::InvalidInput,
+					format!(
+						"states {:?} and {:?} would both be written as {:?}",
+						self.states[other], self.states[state], name
+					),
+				));
+			}
+		}
 		writeln!(writer, "{}", self.states.len())?;
 		writeln!(writer, "{}", self.inputs.len())?;
+		self.write_transitions(writer, &names)
+	}
+
+	fn check_state_names(&self) -> io::Result<()> {
+		match self.states.iter().find(|name| name.starts_with('#')) {
+			Some(name) => Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				format!("state name {:?} cannot start with '#'", name),
+			)),
+			None => Ok(()),
+		}
+	}
+
+	fn write_transitions<W: Write>(&self, writer: &mut W, names: &[String]) -> io::Result<()> {
 		for (from, on, into) in &self.transitions {
 			writeln!(
 				writer,
 				"{} {} {}",
-				self.states[*from], self.inputs[*on], self.states[*into]
+				names[*from], self.inputs[*on], names[*into]
 			)?;
 		}
+		for (from, into) in &self.epsilon_transitions {
+			writeln!(writer, "{} {} {}", names[*from], EPSILON, names[*into])?;
+		}
 		Ok(())
 	}
 }
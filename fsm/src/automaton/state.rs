@@ -1,6 +1,6 @@
 use std::ops;
 
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, Eq, PartialEq)]
 pub struct StateType(u8);
 
 impl StateType {
@@ -21,6 +21,10 @@ impl StateType {
 	pub fn name(self) -> char {
 		StateType::NAMES[self.0 as usize]
 	}
+
+	pub fn reversed(self) -> Self {
+		StateType((self.0 & 1) << 1 | (self.0 & 2) >> 1)
+	}
 }
 
 impl ops::BitAnd for StateType {
@@ -37,8 +41,30 @@ impl ops::BitAndAssign for StateType {
 	}
 }
 
+impl ops::BitOr for StateType {
+	type Output = Self;
+
+	fn bitor(self, rhs: Self) -> Self::Output {
+		StateType(self.0 | rhs.0)
+	}
+}
+
 impl ops::BitOrAssign for StateType {
 	fn bitor_assign(&mut self, rhs: Self) {
 		self.0 |= rhs.0;
 	}
 }
+
+impl ops::Not for StateType {
+	type Output = Self;
+
+	fn not(self) -> Self::Output {
+		StateType(!self.0 & (Self::INITIAL.0 | Self::FINAL.0))
+	}
+}
+
+impl ops::BitXorAssign for StateType {
+	fn bitxor_assign(&mut self, rhs: Self) {
+		self.0 ^= rhs.0;
+	}
+}
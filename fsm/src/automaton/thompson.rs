@@ -0,0 +1,157 @@
+use super::regexp::regexp_inputs;
+use super::{generated_names, Automaton, StateType};
+use crate::regexp::RegExp;
+use std::collections::BTreeSet;
+
+struct Builder {
+	n_states: usize,
+	inputs: Vec<char>,
+	transitions: BTreeSet<(usize, usize, usize)>,
+	epsilon_transitions: BTreeSet<(usize, usize)>,
+}
+
+impl Automaton {
+	pub fn from_regexp_thompson(regexp: &RegExp, alphabet: &[char]) -> Self {
+		let mut builder = Builder {
+			n_states: 0,
+			inputs: regexp_inputs(regexp, alphabet).into_table(),
+			transitions: BTreeSet::new(),
+			epsilon_transitions: BTreeSet::new(),
+		};
+		let (start, accept) = builder.push(regexp);
+		Self {
+			states: generated_names(builder.n_states),
+			state_types: (0..builder.n_states)
+				.map(|state| {
+					let mut state_type = StateType::default();
+					if state == start {
+						state_type |= StateType::INITIAL;
+					}
+					if state == accept {
+						state_type |= StateType::FINAL;
+					}
+					state_type
+				})
+				.collect(),
+			inputs: builder.inputs,
+			transitions: builder.transitions,
+			epsilon_transitions: builder.epsilon_transitions,
+		}
+	}
+}
+
+impl Builder {
+	fn add_state(&mut self) -> usize {
+		self.n_states += 1;
+		self.n_states - 1
+	}
+
+	fn push(&mut self, regexp: &RegExp) -> (usize, usize) {
+		match regexp {
+			RegExp::Empty => (self.add_state(), self.add_state()),
+			RegExp::Epsilon => {
+				let (start, accept) = (self.add_state(), self.add_state());
+				self.epsilon_transitions.insert((start, accept));
+				(start, accept)
+			}
+			RegExp::Concat(parts) => self.push_concat(parts.iter().map(|part| (part, false))),
+			RegExp::Alternation(variants) => {
+				let start = self.add_state();
+				let fragments: Vec<(usize, usize)> =
+					variants.iter().map(|variant| self.push(variant)).collect();
+				let accept = self.add_state();
+				for (first, last) in fragments {
+					self.epsilon_transitions.insert((start, first));
+					self.epsilon_transitions.insert((last, accept));
+				}
+				(start, accept)
+			}
+			RegExp::Star(inner) => self.push_repeated(inner, true, true),
+			RegExp::Plus(inner) => self.push_repeated(inner, false, true),
+			RegExp::Optional(inner) => self.push_repeated(inner, true, false),
+			RegExp::Repeat(..) if regexp.is_empty_repeat() => self.push(&RegExp::Empty),
+			RegExp::Repeat(inner, min, max) => {
+				let star = RegExp::Star(inner.clone());
+				let mut parts = vec![(&**inner, false); *min];
+				match max {
+					Some(max) => parts.resize(*max, (inner, true)),
+					None => parts.push((&star, false)),
+				}
+				self.push_concat(parts)
+			}
+			RegExp::Intersection(_) | RegExp::Complement(_) => {
+				let automaton = Automaton::compose_regexp(regexp, &self.inputs);
+				let (start, accept) = (self.add_state(), self.add_state());
+				let offset = self.n_states;
+				self.n_states += automaton.states.len();
+				for state in 0..automaton.states.len() {
+					if automaton.is_initial(state) {
+						self.epsilon_transitions.insert((start, state + offset));
+					}
+					if automaton.is_final(state) {
+						self.epsilon_transitions.insert((state + offset, accept));
+					}
+				}
+				self.transitions.extend(
+					automaton
+						.transitions
+						.iter()
+						.map(|&(from, on, into)| (from + offset, on, into + offset)),
+				);
+				self.epsilon_transitions.extend(
+					automaton
+						.epsilon_transitions
+						.iter()
+						.map(|&(from, into)| (from + offset, into + offset)),
+				);
+				(start, accept)
+			}
+			symbol => {
+				let chars = symbol.matched_chars(&self.inputs).unwrap();
+				let (start, accept) = (self.add_state(), self.add_state());
+				for c in chars {
+					let on = self.inputs.iter().position(|&input| input == c).unwrap();
+					self.transitions.insert((start, on, accept));
+				}
+				(start, accept)
+			}
+		}
+	}
+
+	fn push_concat<'a, I>(&mut self, parts: I) -> (usize, usize)
+	where
+		I: IntoIterator<Item = (&'a RegExp, bool)>,
+	{
+		let mut fragment: Option<(usize, usize)> = None;
+		for (part, optional) in parts {
+			let (first, last) = if optional {
+				self.push_repeated(part, true, false)
+			} else {
+				self.push(part)
+			};
+			fragment = Some(match fragment {
+				Some((start, accept)) => {
+					self.epsilon_transitions.insert((accept, first));
+					(start, last)
+				}
+				None => (first, last),
+			});
+		}
+		fragment.unwrap_or_else(|| self.push(&RegExp::Epsilon))
+	}
+
+	fn push_repeated(&mut self, inner: &RegExp, skip: bool, repeat: bool) -> (usize, usize) {
+		let start = self.add_state();
+		let (first, last) = self.push(inner);
+		let accept = self.add_state();
+		self.epsilon_transitions.insert((start, first));
+		self.epsilon_transitions.insert((last, accept));
+		if skip {
+			self.epsilon_transitions.insert((start, accept));
+		}
+		if repeat {
+			self.epsilon_transitions.insert((last, first));
+		}
+		(start, accept)
+	}
+}
@@ -0,0 +1,100 @@
+use super::Automaton;
+use crate::regexp::RegExp;
+use std::collections::BTreeMap;
+
+struct Edges {
+	edges: BTreeMap<(usize, usize), RegExp>,
+}
+
+impl Edges {
+	fn add(&mut self, from: usize, into: usize, expr: RegExp) {
+		let expr = match self.edges.remove(&(from, into)) {
+			Some(edge) => edge.alternation(expr),
+			None => expr,
+		};
+		self.edges.insert((from, into), expr);
+	}
+
+	fn predecessors(&self, state: usize) -> Vec<(usize, RegExp)> {
+		self.edges
+			.iter()
+			.filter(|&(&(from, into), _)| into == state && from != state)
+			.map(|(&(from, _), expr)| (from, expr.clone()))
+			.collect()
+	}
+
+	fn successors(&self, state: usize) -> Vec<(usize, RegExp)> {
+		self.edges
+			.range((state, 0)..(state + 1, 0))
+			.filter(|&(&(_, into), _)| into != state)
+			.map(|(&(_, into), expr)| (into, expr.clone()))
+			.collect()
+	}
+
+	fn elimination_cost(&self, state: usize) -> (usize, usize) {
+		let predecessors = self.predecessors(state);
+		let successors = self.successors(state);
+		(
+			predecessors.len() * successors.len(),
+			predecessors
+				.iter()
+				.chain(&successors)
+				.map(|(_, expr)| expr.size())
+				.sum(),
+		)
+	}
+
+	fn eliminate(&mut self, state: usize) {
+		let predecessors = self.predecessors(state);
+		let successors = self.successors(state);
+		let repeated = self
+			.edges
+			.remove(&(state, state))
+			.map_or(RegExp::Epsilon, RegExp::star);
+		self.edges
+			.retain(|&(from, into), _| from != state && into != state);
+		for (from, head) in &predecessors {
+			for (into, tail) in &successors {
+				self.add(
+					*from,
+					*into,
+					head.clone().concat(repeated.clone()).concat(tail.clone()),
+				);
+			}
+		}
+	}
+}
+
+impl Automaton {
+	pub fn to_regexp(&self) -> RegExp {
+		let start = self.states.len();
+		let finish = start + 1;
+		let mut edges = Edges {
+			edges: BTreeMap::new(),
+		};
+		for state in 0..start {
+			if self.is_initial(state) {
+				edges.add(start, state, RegExp::Epsilon);
+			}
+			if self.is_final(state) {
+				edges.add(state, finish, RegExp::Epsilon);
+			}
+		}
+		for &(from, on, into) in &self.transitions {
+			edges.add(from, into, RegExp::Char(self.inputs[on]));
+		}
+		for &(from, into) in &self.epsilon_transitions {
+			edges.add(from, into, RegExp::Epsilon);
+		}
+		let mut remaining: Vec<usize> = (0..start).collect();
+		while let Some(index) =
+			(0..remaining.len()).min_by_key(|&index| edges.elimination_cost(remaining[index]))
+		{
+			edges.eliminate(remaining.swap_remove(index));
+		}
+		edges
+			.edges
+			.remove(&(start, finish))
+			.unwrap_or(RegExp::Empty)
+	}
+}
@@ -0,0 +1,143 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Position {
+	pub line: usize,
+	pub column: usize,
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+	Io(io::Error),
+	MissingValue(Position),
+	InvalidValue(Position, String),
+	UnexpectedToken(Position, String),
+	MissingColumns(Position, usize, usize),
+	ValueOutOfRange(Position, usize, usize),
+	MissingDeclarations(Position),
+	MissingStateName(Position),
+	UnknownStateFlag(Position, String),
+	InvalidInput(Position, String),
+	ReservedEpsilon(Position),
+	DuplicateState(Position, String),
+	InvalidInputDeclaration(Position, char),
+	UndeclaredSymbol(Position, &'static str, String),
+	InvalidTransition(Position, usize),
+	TooManySymbols(Position),
+}
+
+impl LoadError {
+	pub fn position(&self) -> Option<Position> {
+		match self {
+			Self::Io(_) => None,
+			Self::MissingValue(position)
+			| Self::InvalidValue(position, _)
+			| Self::UnexpectedToken(position, _)
+			| Self::MissingColumns(position, _, _)
+			| Self::ValueOutOfRange(position, _, _)
+			| Self::MissingDeclarations(position)
+			| Self::MissingStateName(position)
+			| Self::UnknownStateFlag(position, _)
+			| Self::InvalidInput(position, _)
+			| Self::ReservedEpsilon(position)
+			| Self::DuplicateState(position, _)
+			| Self::InvalidInputDeclaration(position, _)
+			| Self::UndeclaredSymbol(position, _, _)
+			| Self::InvalidTransition(position, _)
+			| Self::TooManySymbols(position) => Some(*position),
+		}
+	}
+
+	pub fn with_file(self, file: &str) -> io::Error {
+		match self {
+			Self::Io(err) => err,
+			_ => io::Error::new(io::ErrorKind::InvalidData, format!("{}:{}", file, self)),
+		}
+	}
+}
+
+impl fmt::Display for Position {
+	fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		write!(formatter, "{}:{}", self.line, self.column)
+	}
+}
+
+impl fmt::Display for LoadError {
+	fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		if let Some(position) = self.position() {
+			write!(formatter, "{}: ", position)?;
+		}
+		match self {
+			Self::Io(err) => write!(formatter, "{}", err),
+			Self::MissingValue(_) => formatter.write_str("expected value"),
+			Self::InvalidValue(_, message) => formatter.write_str(message),
+			Self::UnexpectedToken(_, token) => write!(formatter, "unexpected token '{}'", token),
+			Self::MissingColumns(_, expected, found) => {
+				write!(formatter, "expected {} columns, found {}", expected, found)
+			}
+			Self::ValueOutOfRange(_, value, limit) => write!(
+				formatter,
+				"expected any non-negative integer below {}, found {}",
+				limit, value
+			),
+			Self::MissingDeclarations(_) => {
+				formatter.write_str("fewer declarations than declared count")
+			}
+			Self::MissingStateName(_) => formatter.write_str("no state name specified"),
+			Self::UnknownStateFlag(_, flag) => write!(formatter, "unknown state flag '{}'", flag),
+			Self::InvalidInput(_, input) => write!(
+				formatter,
+				"input should be single character, found '{}'",
+				input
+			),
+			Self::ReservedEpsilon(_) => write!(
+				formatter,
+				"'{}' is reserved for epsilon transitions",
+				crate::EPSILON
+			),
+			Self::DuplicateState(_, name) => {
+				write!(formatter, "state '{}' is declared twice", name)
+			}
+			Self::InvalidInputDeclaration(_, input) => {
+				write!(formatter, "input '{}' can't be declared", input)
+			}
+			Self::UndeclaredSymbol(_, what, name) => {
+				write!(formatter, "{} '{}' is used but not declared", what, name)
+			}
+			Self::InvalidTransition(_, found) => write!(
+				formatter,
+				"transition definition should contain 3 arguments, found {}",
+				found
+			),
+			Self::TooManySymbols(_) => {
+				formatter.write_str("more states or inputs are used than declared")
+			}
+		}
+	}
+}
+
+impl Error for LoadError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match self {
+			Self::Io(err) => Some(err),
+			_ => None,
+		}
+	}
+}
+
+impl From<io::Error> for LoadError {
+	fn from(err: io::Error) -> Self {
+		Self::Io(err)
+	}
+}
+
+impl From<LoadError> for io::Error {
+	fn from(err: LoadError) -> Self {
+		match err {
+			LoadError::Io(err) => err,
+			_ => io::Error::new(io::ErrorKind::InvalidData, err),
+		}
+	}
+}
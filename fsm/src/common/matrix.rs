@@ -1,13 +1,16 @@
+use super::{LoadError, TokenReader};
 use std::fmt::Display;
-use std::io::{BufRead, Error, ErrorKind, Result};
+use std::io::BufRead;
 use std::str::FromStr;
 
-pub fn parse_array0<R: BufRead, T: FromStr>(reader: &mut R, line: &mut String) -> Result<T>
+pub fn parse_array0<R: BufRead, T: FromStr>(reader: &mut TokenReader<R>) -> Result<T, LoadError>
 where
 	T::Err: Display,
 {
-	reader.read_line(line)?;
-	let parsed = line.trim().parse();
-	line.clear();
-	parsed.map_err(|err: T::Err| Error::new(ErrorKind::InvalidData, err.to_string()))
+	let (position, token) = reader
+		.next_token()?
+		.ok_or_else(|| LoadError::MissingValue(reader.position()))?;
+	token
+		.parse()
+		.map_err(|err: T::Err| LoadError::InvalidValue(position, err.to_string()))
 }
@@ -1,4 +1,8 @@
+mod error;
 mod matrix;
 mod symbols;
+mod text;
+pub use error::{LoadError, Position};
 pub use matrix::parse_array0;
 pub use symbols::Symbols;
+pub use text::TokenReader;
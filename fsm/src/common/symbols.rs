@@ -25,10 +25,22 @@ impl<T> Symbols<T> {
 		self.name_by_id.len()
 	}
 
+	pub fn is_empty(&self) -> bool {
+		self.name_by_id.is_empty()
+	}
+
+	pub fn table(&self) -> &[T] {
+		&self.name_by_id
+	}
+
 	pub fn into_table(self) -> Vec<T> {
 		self.name_by_id
 	}
 
+	pub fn get_name(&self, id: usize) -> &T {
+		&self.name_by_id[id]
+	}
+
 	pub fn get_id<B>(&self, name: &B) -> Option<&usize>
 	where
 		T: Borrow<B> + Ord,
@@ -52,3 +64,9 @@ impl<T> Symbols<T> {
 		}
 	}
 }
+
+impl<T> Default for Symbols<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,94 @@
+use super::{LoadError, Position};
+use std::io::BufRead;
+
+pub struct TokenReader<R> {
+	reader: R,
+	line: String,
+	line_number: usize,
+	offset: usize,
+	comments: bool,
+}
+
+impl<R: BufRead> TokenReader<R> {
+	pub fn new(reader: R) -> Self {
+		Self {
+			reader,
+			line: String::new(),
+			line_number: 0,
+			offset: 0,
+			comments: true,
+		}
+	}
+
+	pub fn without_comments(reader: R) -> Self {
+		Self {
+			comments: false,
+			..Self::new(reader)
+		}
+	}
+
+	pub fn position(&self) -> Position {
+		Position {
+			line: self.line_number.max(1),
+			column: self.line[..self.offset].chars().count() + 1,
+		}
+	}
+
+	pub fn next_token(&mut self) -> Result<Option<(Position, String)>, LoadError> {
+		self.read_token(self.comments)
+	}
+
+	pub fn next_raw_token(&mut self) -> Result<Option<(Position, String)>, LoadError> {
+		self.read_token(false)
+	}
+
+	fn read_token(&mut self, comments: bool) -> Result<Option<(Position, String)>, LoadError> {
+		loop {
+			if let Some(token) = self.scan(comments) {
+				return Ok(Some(token));
+			}
+			if !self.read_line()? {
+				return Ok(None);
+			}
+		}
+	}
+
+	fn read_line(&mut self) -> Result<bool, LoadError> {
+		let mut line = String::new();
+		if self.reader.read_line(&mut line)? == 0 {
+			return Ok(false);
+		}
+		self.line = line;
+		self.line_number += 1;
+		self.offset = 0;
+		Ok(true)
+	}
+
+	pub fn next_token_on_line(&mut self) -> Option<(Position, String)> {
+		self.scan(self.comments)
+	}
+
+	pub fn next_raw_token_on_line(&mut self) -> Option<(Position, String)> {
+		self.scan(false)
+	}
+
+	pub fn at_line_end(&self) -> bool {
+		let rest = self.line[self.offset..].trim_start();
+		rest.is_empty() || self.comments && rest.starts_with('#')
+	}
+
+	fn scan(&mut self, comments: bool) -> Option<(Position, String)> {
+		let rest = &self.line[self.offset..];
+		self.offset += rest.len() - rest.trim_start().len();
+		let rest = &self.line[self.offset..];
+		if rest.is_empty() || comments && rest.starts_with('#') {
+			self.offset = self.line[..self.offset].trim_end().len();
+			return None;
+		}
+		let length = rest.find(char::is_whitespace).unwrap_or(rest.len());
+		let position = self.position();
+		let token = rest[..length].to_string();
+		self.offset += length;
+		Some((position, token))
+	}
+}
@@ -3,14 +3,14 @@ mod matrix;
 mod minimize;
 mod model;
 mod simple_text;
-use model::Model;
+pub(crate) use model::Model;
 use ndarray::Array2;
 
 pub struct DFA {
-	model: Model,
-	n_states: usize,
-	n_inputs: usize,
-	n_outputs: usize,
-	output_matrix: Array2<usize>,
-	state_matrix: Array2<usize>,
+	pub(crate) model: Model,
+	pub(crate) n_states: usize,
+	pub(crate) n_inputs: usize,
+	pub(crate) n_outputs: usize,
+	pub(crate) output_matrix: Array2<usize>,
+	pub(crate) state_matrix: Array2<usize>,
 }
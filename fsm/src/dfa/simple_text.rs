@@ -1,45 +1,32 @@
 use super::{matrix, Model, DFA};
-use crate::common::parse_array0;
+use crate::common::{parse_array0, LoadError, TokenReader};
 use ndarray::{ArrayView1, ArrayViewMut1};
-use std::io::{BufRead, Error, ErrorKind, Result, Write};
+use std::io::{self, BufRead, Write};
 use std::mem::MaybeUninit;
+use std::num::ParseIntError;
 
 fn parse_array1<R: BufRead>(
-	reader: &mut R,
-	line: &mut String,
+	reader: &mut TokenReader<R>,
 	array: &mut ArrayViewMut1<MaybeUninit<usize>>,
 	value_limit: usize,
-) -> Result<()> {
-	reader.read_line(line)?;
-	let mut iter = line.split_whitespace();
+) -> Result<(), LoadError> {
+	let n_columns = array.len();
 	for (index, cell) in array.iter_mut().enumerate() {
-		let value = match iter.next() {
-			Some(value) => value,
-			None => {
-				return Err(Error::new(
-					ErrorKind::InvalidData,
-					format!("expected {} columns, found {}", array.len(), index),
-				))
-			}
-		}
-		.parse()
-		.map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+		let (position, token) = reader
+			.next_token()?
+			.ok_or_else(|| LoadError::MissingColumns(reader.position(), n_columns, index))?;
+		let value = token
+			.parse()
+			.map_err(|err: ParseIntError| LoadError::InvalidValue(position, err.to_string()))?;
 		if value >= value_limit {
-			return Err(Error::new(
-				ErrorKind::InvalidData,
-				format!(
-					"expected any non-negative integer below {}, found {}",
-					value_limit, value
-				),
-			));
+			return Err(LoadError::ValueOutOfRange(position, value, value_limit));
 		}
 		*cell = MaybeUninit::new(value);
 	}
-	line.clear();
 	Ok(())
 }
 
-fn format_array1<W: Write>(writer: &mut W, array: &ArrayView1<usize>) -> Result<()> {
+fn format_array1<W: Write>(writer: &mut W, array: &ArrayView1<usize>) -> io::Result<()> {
 	for cell in array {
 		write!(writer, "{} ", cell)?;
 	}
@@ -47,12 +34,12 @@ fn format_array1<W: Write>(writer: &mut W, array: &ArrayView1<usize>) -> Result<
 }
 
 impl DFA {
-	pub fn load_from_simple_text<R: BufRead>(reader: &mut R) -> Result<Self> {
-		let mut line = String::new();
-		let model = parse_array0(reader, &mut line)?;
-		let n_states = parse_array0(reader, &mut line)?;
-		let n_inputs = parse_array0(reader, &mut line)?;
-		let n_outputs = parse_array0(reader, &mut line)?;
+	pub fn load_from_simple_text<R: BufRead>(reader: &mut R) -> Result<Self, LoadError> {
+		let mut reader = TokenReader::new(reader);
+		let model = parse_array0(&mut reader)?;
+		let n_states = parse_array0(&mut reader)?;
+		let n_inputs = parse_array0(&mut reader)?;
+		let n_outputs = parse_array0(&mut reader)?;
 		let mut output_matrix = matrix::uninit_output(model, n_states, n_inputs);
 		let mut state_matrix = matrix::uninit_state(n_states, n_inputs);
 		if model == Model::Mealy {
@@ -61,20 +48,18 @@ impl DFA {
 				.into_iter()
 				.zip(output_matrix.columns_mut())
 			{
-				parse_array1(reader, &mut line, &mut states, n_states)?;
-				parse_array1(reader, &mut line, &mut outputs, n_outputs)?;
+				parse_array1(&mut reader, &mut states, n_states)?;
+				parse_array1(&mut reader, &mut outputs, n_outputs)?;
 			}
 		} else {
-			parse_array1(
-				reader,
-				&mut line,
-				&mut output_matrix.column_mut(0),
-				n_outputs,
-			)?;
+			parse_array1(&mut reader, &mut output_matrix.column_mut(0), n_outputs)?;
 			for mut states in state_matrix.columns_mut() {
-				parse_array1(reader, &mut line, &mut states, n_states)?;
+				parse_array1(&mut reader, &mut states, n_states)?;
 			}
 		}
+		if let Some((position, token)) = reader.next_token()? {
+			return Err(LoadError::UnexpectedToken(position, token));
+		}
 		unsafe {
 			Ok(Self {
 				model,
@@ -87,7 +72,7 @@ impl DFA {
 		}
 	}
 
-	pub fn store_as_simple_text<W: Write>(&self, writer: &mut W) -> Result<()> {
+	pub fn store_as_simple_text<W: Write>(&self, writer: &mut W) -> io::Result<()> {
 		writeln!(writer, "{}", self.model)?;
 		writeln!(writer, "{}", self.n_states)?;
 		writeln!(writer, "{}", self.n_inputs)?;
@@ -1,6 +1,8 @@
 mod automaton;
 mod common;
 mod dfa;
-pub use automaton::Automaton;
-pub use common::Symbols;
+mod regexp;
+pub use automaton::{Automaton, AutomatonBuilder, GrammarType, EPSILON};
+pub use common::{LoadError, Position, Symbols};
 pub use dfa::DFA;
+pub use regexp::{Diagnostic, ParseRegExpError, RegExp};
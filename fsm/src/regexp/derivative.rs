@@ -0,0 +1,205 @@
+use super::RegExp;
+use std::collections::BTreeSet;
+
+impl RegExp {
+	pub fn is_nullable(&self) -> bool {
+		match self {
+			Self::Empty | Self::Char(_) | Self::Class(_, _) | Self::Any => false,
+			Self::Epsilon | Self::Star(_) | Self::Optional(_) => true,
+			Self::Concat(parts) | Self::Intersection(parts) => parts.iter().all(Self::is_nullable),
+			Self::Alternation(variants) => variants.iter().any(Self::is_nullable),
+			Self::Complement(inner) => !inner.is_nullable(),
+			Self::Plus(inner) => inner.is_nullable(),
+			Self::Repeat(..) if self.is_empty_repeat() => false,
+			Self::Repeat(inner, min, _) => *min == 0 || inner.is_nullable(),
+		}
+	}
+
+	pub fn derivative(&self, c: char, alphabet: &[char]) -> Self {
+		match self {
+			Self::Empty | Self::Epsilon => Self::Empty,
+			Self::Concat(parts) if parts.is_empty() => Self::Empty,
+			Self::Concat(parts) => {
+				let (head, tail) = parts.split_first().unwrap();
+				let tail = Self::similar_concat(tail.to_vec());
+				let derivative = head.derivative(c, alphabet).similar_then(tail.clone());
+				if head.is_nullable() {
+					Self::similar_alternation(vec![derivative, tail.derivative(c, alphabet)])
+				} else {
+					derivative
+				}
+			}
+			Self::Alternation(variants) => Self::similar_alternation(
+				variants
+					.iter()
+					.map(|variant| variant.derivative(c, alphabet))
+					.collect(),
+			),
+			Self::Intersection(operands) => Self::similar_intersection(
+				operands
+					.iter()
+					.map(|operand| operand.derivative(c, alphabet))
+					.collect(),
+			),
+			Self::Complement(inner) => match inner.derivative(c, alphabet) {
+				Self::Complement(inner) => *inner,
+				derivative => Self::Complement(Box::new(derivative)),
+			},
+			Self::Star(inner) | Self::Plus(inner) => inner
+				.derivative(c, alphabet)
+				.similar_then(Self::Star(inner.clone())),
+			Self::Optional(inner) => inner.derivative(c, alphabet),
+			Self::Repeat(inner, min, max) => match inner.remaining(*min, *max) {
+				Some(remaining) => inner.derivative(c, alphabet).similar_then(remaining),
+				None => Self::Empty,
+			},
+			symbol => {
+				if symbol.matched_chars(alphabet).unwrap().contains(&c) {
+					Self::Epsilon
+				} else {
+					Self::Empty
+				}
+			}
+		}
+	}
+
+	pub fn partial_derivatives(&self, c: char, alphabet: &[char]) -> BTreeSet<Self> {
+		match self {
+			Self::Empty | Self::Epsilon => BTreeSet::new(),
+			Self::Concat(parts) if parts.is_empty() => BTreeSet::new(),
+			Self::Concat(parts) => {
+				let (head, tail) = parts.split_first().unwrap();
+				let tail = Self::similar_concat(tail.to_vec());
+				let mut derivatives: BTreeSet<Self> = head
+					.partial_derivatives(c, alphabet)
+					.into_iter()
+					.map(|derivative| derivative.similar_then(tail.clone()))
+					.collect();
+				if head.is_nullable() {
+					derivatives.append(&mut tail.partial_derivatives(c, alphabet));
+				}
+				derivatives
+			}
+			Self::Alternation(variants) => variants
+				.iter()
+				.flat_map(|variant| variant.partial_derivatives(c, alphabet))
+				.collect(),
+			Self::Intersection(operands) => {
+				let mut derivatives = BTreeSet::from([Self::Intersection(Vec::new())]);
+				for operand in operands {
+					let operand_derivatives = operand.partial_derivatives(c, alphabet);
+					derivatives = derivatives
+						.iter()
+						.flat_map(|derivative| {
+							operand_derivatives.iter().map(move |operand_derivative| {
+								Self::similar_intersection(vec![
+									derivative.clone(),
+									operand_derivative.clone(),
+								])
+							})
+						})
+						.collect();
+				}
+				derivatives
+			}
+			Self::Complement(inner) => {
+				BTreeSet::from([Self::Complement(Box::new(Self::similar_alternation(
+					inner.partial_derivatives(c, alphabet).into_iter().collect(),
+				)))])
+			}
+			Self::Star(inner) | Self::Plus(inner) => inner
+				.partial_derivatives(c, alphabet)
+				.into_iter()
+				.map(|derivative| derivative.similar_then(Self::Star(inner.clone())))
+				.collect(),
+			Self::Optional(inner) => inner.partial_derivatives(c, alphabet),
+			Self::Repeat(inner, min, max) => match inner.remaining(*min, *max) {
+				Some(remaining) => inner
+					.partial_derivatives(c, alphabet)
+					.into_iter()
+					.map(|derivative| derivative.similar_then(remaining.clone()))
+					.collect(),
+				None => BTreeSet::new(),
+			},
+			symbol => {
+				if symbol.matched_chars(alphabet).unwrap().contains(&c) {
+					BTreeSet::from([Self::Epsilon])
+				} else {
+					BTreeSet::new()
+				}
+			}
+		}
+	}
+
+	fn remaining(&self, min: usize, max: Option<usize>) -> Option<Self> {
+		let max = match max {
+			Some(max) if max == 0 || max < min => return None,
+			Some(max) => Some(max - 1),
+			None => None,
+		};
+		Some(match (min.saturating_sub(1), max) {
+			(_, Some(0)) => Self::Epsilon,
+			(0, None) => Self::Star(Box::new(self.clone())),
+			(1, Some(1)) => self.clone(),
+			(min, max) => Self::Repeat(Box::new(self.clone()), min, max),
+		})
+	}
+
+	fn similar_then(self, rhs: Self) -> Self {
+		Self::similar_concat(vec![self, rhs])
+	}
+
+	fn similar_concat(parts: Vec<Self>) -> Self {
+		let mut flattened = Vec::new();
+		for part in parts {
+			match part {
+				Self::Empty => return Self::Empty,
+				Self::Epsilon => {}
+				Self::Concat(inner) => flattened.extend(inner),
+				part => flattened.push(part),
+			}
+		}
+		match flattened.len() {
+			0 => Self::Epsilon,
+			1 => flattened.pop().unwrap(),
+			_ => Self::Concat(flattened),
+		}
+	}
+
+	fn similar_alternation(variants: Vec<Self>) -> Self {
+		let mut flattened = BTreeSet::new();
+		for variant in variants {
+			match variant {
+				Self::Empty => {}
+				Self::Alternation(inner) => flattened.extend(inner),
+				variant => {
+					flattened.insert(variant);
+				}
+			}
+		}
+		let mut flattened: Vec<Self> = flattened.into_iter().collect();
+		match flattened.len() {
+			0 => Self::Empty,
+			1 => flattened.pop().unwrap(),
+			_ => Self::Alternation(flattened),
+		}
+	}
+
+	fn similar_intersection(operands: Vec<Self>) -> Self {
+		let mut flattened = BTreeSet::new();
+		for operand in operands {
+			match operand {
+				Self::Empty => return Self::Empty,
+				Self::Intersection(inner) => flattened.extend(inner),
+				operand => {
+					flattened.insert(operand);
+				}
+			}
+		}
+		let mut flattened: Vec<Self> = flattened.into_iter().collect();
+		match flattened.len() {
+			1 => flattened.pop().unwrap(),
+			_ => Self::Intersection(flattened),
+		}
+	}
+}
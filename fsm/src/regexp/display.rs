@@ -0,0 +1,121 @@
+use super::RegExp;
+use std::fmt;
+
+const ALTERNATION: u8 = 0;
+const INTERSECTION: u8 = 1;
+const CONCAT: u8 = 2;
+const COMPLEMENT: u8 = 3;
+const POSTFIX: u8 = 4;
+const ATOM: u8 = 5;
+
+impl RegExp {
+	fn precedence(&self) -> u8 {
+		match self {
+			Self::Alternation(_) => ALTERNATION,
+			Self::Intersection(_) => INTERSECTION,
+			Self::Concat(_) => CONCAT,
+			Self::Complement(_) => COMPLEMENT,
+			Self::Star(_) | Self::Plus(_) | Self::Optional(_) | Self::Repeat(_, _, _) => POSTFIX,
+			_ => ATOM,
+		}
+	}
+
+	fn fmt_operand(&self, formatter: &mut fmt::Formatter, precedence: u8) -> fmt::Result {
+		match self {
+			Self::Epsilon if precedence > INTERSECTION => formatter.write_str("()"),
+			_ if self.precedence() < precedence => write!(formatter, "({})", self),
+			_ => write!(formatter, "{}", self),
+		}
+	}
+
+	fn fmt_operands(
+		formatter: &mut fmt::Formatter,
+		operands: &[Self],
+		separator: &str,
+		precedence: u8,
+	) -> fmt::Result {
+		for (index, operand) in operands.iter().enumerate() {
+			if index != 0 {
+				formatter.write_str(separator)?;
+			}
+			operand.fmt_operand(formatter, precedence)?;
+		}
+		Ok(())
+	}
+}
+
+impl fmt::Display for RegExp {
+	fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Empty => formatter.write_str("∅"),
+			Self::Epsilon => Ok(()),
+			Self::Char(c) => {
+				if "&()*+.?[]{}|~\\∅".contains(*c) {
+					formatter.write_str("\\")?;
+				}
+				write!(formatter, "{}", c)
+			}
+			Self::Class(false, chars) if chars.is_empty() => formatter.write_str("∅"),
+			Self::Class(true, chars) if chars.is_empty() => formatter.write_str("."),
+			Self::Class(negated, chars) => {
+				formatter.write_str(if *negated { "[^" } else { "[" })?;
+				let chars: Vec<char> = chars.iter().copied().collect();
+				let mut index = 0;
+				while index < chars.len() {
+					let first = chars[index];
+					let mut last = index;
+					while last + 1 < chars.len() && chars[last] as u32 + 1 == chars[last + 1] as u32
+					{
+						last += 1;
+					}
+					write_class_char(formatter, first)?;
+					if last >= index + 2 {
+						formatter.write_str("-")?;
+						write_class_char(formatter, chars[last])?;
+						index = last + 1;
+					} else {
+						index += 1;
+					}
+				}
+				formatter.write_str("]")
+			}
+			Self::Any => formatter.write_str("."),
+			Self::Concat(parts) => Self::fmt_operands(formatter, parts, "", CONCAT),
+			Self::Alternation(variants) => {
+				Self::fmt_operands(formatter, variants, "|", INTERSECTION)
+			}
+			Self::Intersection(operands) => Self::fmt_operands(formatter, operands, "&", CONCAT),
+			Self::Complement(inner) => {
+				formatter.write_str("~")?;
+				inner.fmt_operand(formatter, COMPLEMENT)
+			}
+			Self::Star(inner) => {
+				inner.fmt_operand(formatter, POSTFIX)?;
+				formatter.write_str("*")
+			}
+			Self::Plus(inner) => {
+				inner.fmt_operand(formatter, POSTFIX)?;
+				formatter.write_str("+")
+			}
+			Self::Optional(inner) => {
+				inner.fmt_operand(formatter, POSTFIX)?;
+				formatter.write_str("?")
+			}
+			Self::Repeat(inner, min, max) => {
+				inner.fmt_operand(formatter, POSTFIX)?;
+				match max {
+					Some(max) if max == min => write!(formatter, "{{{}}}", min),
+					Some(max) => write!(formatter, "{{{},{}}}", min, max),
+					None => write!(formatter, "{{{},}}", min),
+				}
+			}
+		}
+	}
+}
+
+fn write_class_char(formatter: &mut fmt::Formatter, c: char) -> fmt::Result {
+	if "]\\^-".contains(c) {
+		formatter.write_str("\\")?;
+	}
+	write!(formatter, "{}", c)
+}
@@ -0,0 +1,110 @@
+mod derivative;
+mod display;
+mod parse;
+mod simplify;
+
+pub use parse::{Diagnostic, ParseRegExpError};
+use std::collections::BTreeSet;
+
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum RegExp {
+	Empty,
+	Epsilon,
+	Char(char),
+	Class(bool, BTreeSet<char>),
+	Any,
+	Concat(Vec<RegExp>),
+	Alternation(Vec<RegExp>),
+	Intersection(Vec<RegExp>),
+	Complement(Box<RegExp>),
+	Star(Box<RegExp>),
+	Plus(Box<RegExp>),
+	Optional(Box<RegExp>),
+	/// `inner{min,max}`; bounds with `max < min` match nothing.
+	///
+	/// ```
+	/// use fsm::{Automaton, RegExp};
+	///
+	/// let repeat = RegExp::Repeat(Box::new(RegExp::Char('a')), 3, Some(2));
+	/// let extended = RegExp::Intersection(vec![repeat.clone()]);
+	/// for automaton in [
+	///     Automaton::from_parsed_regexp(&repeat, &[]),
+	///     Automaton::from_parsed_regexp(&extended, &[]),
+	///     Automaton::from_regexp_thompson(&repeat, &[]),
+	///     Automaton::from_regexp_brzozowski(&repeat, &['a']),
+	///     Automaton::from_regexp_antimirov(&repeat, &['a']),
+	/// ] {
+	///     assert!((0..5).all(|n| !automaton.accepts(&"a".repeat(n))));
+	/// }
+	/// assert_eq!(repeat.simplify(), RegExp::Empty);
+	/// ```
+	Repeat(Box<RegExp>, usize, Option<usize>),
+}
+
+impl RegExp {
+	pub fn alphabet(&self) -> Vec<char> {
+		let mut alphabet = Vec::new();
+		self.collect_alphabet(&mut alphabet);
+		alphabet
+	}
+
+	pub(crate) fn is_empty_repeat(&self) -> bool {
+		matches!(self, Self::Repeat(_, min, Some(max)) if max < min)
+	}
+
+	pub fn is_extended(&self) -> bool {
+		match self {
+			Self::Intersection(_) | Self::Complement(_) => true,
+			Self::Concat(parts) | Self::Alternation(parts) => parts.iter().any(Self::is_extended),
+			Self::Star(inner)
+			| Self::Plus(inner)
+			| Self::Optional(inner)
+			| Self::Repeat(inner, _, _) => inner.is_extended(),
+			_ => false,
+		}
+	}
+
+	pub fn matched_chars(&self, alphabet: &[char]) -> Option<Vec<char>> {
+		match self {
+			Self::Char(c) => Some(vec![*c]),
+			Self::Class(false, chars) => Some(chars.iter().copied().collect()),
+			Self::Class(true, chars) => Some(
+				alphabet
+					.iter()
+					.copied()
+					.filter(|c| !chars.contains(c))
+					.collect(),
+			),
+			Self::Any => Some(alphabet.to_vec()),
+			_ => None,
+		}
+	}
+
+	fn collect_alphabet(&self, alphabet: &mut Vec<char>) {
+		match self {
+			Self::Char(c) => {
+				if !alphabet.contains(c) {
+					alphabet.push(*c);
+				}
+			}
+			Self::Class(_, chars) => {
+				for c in chars {
+					if !alphabet.contains(c) {
+						alphabet.push(*c);
+					}
+				}
+			}
+			Self::Concat(parts) | Self::Alternation(parts) | Self::Intersection(parts) => {
+				for part in parts {
+					part.collect_alphabet(alphabet);
+				}
+			}
+			Self::Complement(inner)
+			| Self::Star(inner)
+			| Self::Plus(inner)
+			| Self::Optional(inner)
+			| Self::Repeat(inner, _, _) => inner.collect_alphabet(alphabet),
+			Self::Empty | Self::Epsilon | Self::Any => {}
+		}
+	}
+}
@@ -0,0 +1,487 @@
+use super::RegExp;
+use crate::EPSILON;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::iter::{Peekable, Zip};
+use std::ops::{Range, RangeFrom};
+use std::str::Chars;
+use std::vec::IntoIter;
+
+type RegExpChars<'a> = Peekable<Zip<Chars<'a>, RangeFrom<usize>>>;
+
+enum Token {
+	Char(char),
+	Class(bool, BTreeSet<char>),
+	Any,
+	Empty,
+	StartGroup,
+	EndGroup,
+	Star,
+	Plus,
+	Optional,
+	Repeat(usize, Option<usize>),
+	Alternative,
+	Intersection,
+	Complement,
+}
+
+struct Parser {
+	tokens: Peekable<IntoIter<(Range<usize>, Token)>>,
+	diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug)]
+pub struct ParseRegExpError {
+	diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug)]
+pub struct Diagnostic {
+	span: Range<usize>,
+	kind: RegExpErrorKind,
+}
+
+#[derive(Debug)]
+enum RegExpErrorKind {
+	MissingPostfixUnaryOpArg(char),
+	MissingPrefixUnaryOpArg(char),
+	UnmatchedParenthesis,
+	InvalidEscapeSequence,
+	UnterminatedClass,
+	ReversedRange(char, char),
+	UnterminatedRepetition,
+	InvalidRepetitionBound,
+	ReversedRepetitionBounds(usize, usize),
+	ReservedEpsilon,
+	EmptyGroup,
+	TrailingAlternative,
+}
+
+impl RegExp {
+	pub fn parse(regexp: &str) -> Result<Self, ParseRegExpError> {
+		Self::parse_with_warnings(regexp, false).map(|(regexp, _)| regexp)
+	}
+
+	pub fn parse_extended(regexp: &str) -> Result<Self, ParseRegExpError> {
+		Self::parse_with_warnings(regexp, true).map(|(regexp, _)| regexp)
+	}
+
+	pub fn parse_with_warnings(
+		regexp: &str,
+		extended: bool,
+	) -> Result<(Self, Vec<Diagnostic>), ParseRegExpError> {
+		let mut diagnostics = Vec::new();
+		let tokens = tokenize(regexp, extended, &mut diagnostics);
+		let mut parser = Parser {
+			tokens: tokens.into_iter().peekable(),
+			diagnostics,
+		};
+		let regexp = parser.parse_alternation();
+		while let Some((span, _)) = parser.tokens.next() {
+			parser.report(span, RegExpErrorKind::UnmatchedParenthesis);
+			parser.parse_alternation();
+		}
+		let mut diagnostics = parser.diagnostics;
+		diagnostics.sort_by_key(|diagnostic| diagnostic.span.start);
+		if diagnostics.iter().all(Diagnostic::is_warning) {
+			Ok((regexp, diagnostics))
+		} else {
+			Err(ParseRegExpError { diagnostics })
+		}
+	}
+}
+
+impl ParseRegExpError {
+	pub fn diagnostics(&self) -> &[Diagnostic] {
+		&self.diagnostics
+	}
+
+	pub fn into_diagnostics(self) -> Vec<Diagnostic> {
+		self.diagnostics
+	}
+}
+
+impl Diagnostic {
+	pub fn span(&self) -> Range<usize> {
+		self.span.clone()
+	}
+
+	pub fn shift(&mut self, offset: usize) {
+		self.span = self.span.start + offset..self.span.end + offset;
+	}
+
+	pub fn is_warning(&self) -> bool {
+		matches!(
+			self.kind,
+			RegExpErrorKind::EmptyGroup | RegExpErrorKind::TrailingAlternative
+		)
+	}
+
+	pub fn render(&self, source: &str, location: &str) -> String {
+		let underline: String = source
+			.chars()
+			.take(self.span.start)
+			.map(|c| if c == '\t' { '\t' } else { ' ' })
+			.chain(std::iter::repeat_n('^', self.span.len().max(1)))
+			.collect();
+		format!("{}:{}\n{}\n{}", location, self, source, underline)
+	}
+}
+
+fn tokenize(
+	regexp: &str,
+	extended: bool,
+	diagnostics: &mut Vec<Diagnostic>,
+) -> Vec<(Range<usize>, Token)> {
+	let mut tokens = Vec::new();
+	let length = regexp.chars().count();
+	let mut chars = regexp.chars().zip(0..).peekable();
+	while let Some((c, start)) = chars.next() {
+		let token = match c {
+			'(' => Ok(Token::StartGroup),
+			')' => Ok(Token::EndGroup),
+			'*' => Ok(Token::Star),
+			'+' => Ok(Token::Plus),
+			'?' => Ok(Token::Optional),
+			'{' => parse_repetition(&mut chars, start, length),
+			'|' => Ok(Token::Alternative),
+			'&' if extended => Ok(Token::Intersection),
+			'~' if extended => Ok(Token::Complement),
+			'.' => Ok(Token::Any),
+			'∅' => Ok(Token::Empty),
+			'[' => parse_class(&mut chars, start, length, diagnostics),
+			'\\' => parse_escaped(&mut chars, start).map(Token::Char),
+			_ => Ok(Token::Char(c)),
+		};
+		let token = match token {
+			Ok(Token::Char(EPSILON)) => Err(Diagnostic {
+				span: start..chars.peek().map_or(length, |&(_, end)| end),
+				kind: RegExpErrorKind::ReservedEpsilon,
+			}),
+			token => token,
+		};
+		let end = chars.peek().map_or(length, |&(_, end)| end);
+		match token {
+			Ok(token) => tokens.push((start..end, token)),
+			Err(diagnostic) => diagnostics.push(diagnostic),
+		}
+	}
+	tokens
+}
+
+fn parse_escaped(chars: &mut RegExpChars, start: usize) -> Result<char, Diagnostic> {
+	chars.next().map(|(c, _)| c).ok_or(Diagnostic {
+		span: start..start + 1,
+		kind: RegExpErrorKind::InvalidEscapeSequence,
+	})
+}
+
+fn parse_bound(chars: &mut RegExpChars) -> Result<Option<usize>, Diagnostic> {
+	let mut bound = None;
+	while let Some((digit, position)) = chars.next_if(|&(c, _)| c.is_ascii_digit()) {
+		bound = Some(
+			bound
+				.unwrap_or(0usize)
+				.checked_mul(10)
+				.and_then(|bound| bound.checked_add(digit.to_digit(10).unwrap() as usize))
+				.ok_or(Diagnostic {
+					span: position..position + 1,
+					kind: RegExpErrorKind::InvalidRepetitionBound,
+				})?,
+		);
+	}
+	Ok(bound)
+}
+
+fn parse_repetition(
+	chars: &mut RegExpChars,
+	start: usize,
+	length: usize,
+) -> Result<Token, Diagnostic> {
+	let min = parse_bound(chars)?;
+	let max = match chars.next() {
+		Some(('}', _)) if min.is_some() => min,
+		Some((',', _)) if min.is_some() => {
+			let max = parse_bound(chars)?;
+			match chars.next() {
+				Some(('}', _)) => max,
+				Some((_, position)) => {
+					return Err(Diagnostic {
+						span: position..position + 1,
+						kind: RegExpErrorKind::InvalidRepetitionBound,
+					})
+				}
+				None => {
+					return Err(Diagnostic {
+						span: start..length,
+						kind: RegExpErrorKind::UnterminatedRepetition,
+					})
+				}
+			}
+		}
+		Some((_, position)) => {
+			return Err(Diagnostic {
+				span: position..position + 1,
+				kind: RegExpErrorKind::InvalidRepetitionBound,
+			})
+		}
+		None => {
+			return Err(Diagnostic {
+				span: start..length,
+				kind: RegExpErrorKind::UnterminatedRepetition,
+			})
+		}
+	};
+	let min = min.unwrap();
+	match max {
+		Some(max) if max < min => Err(Diagnostic {
+			span: start..chars.peek().map_or(length, |&(_, end)| end),
+			kind: RegExpErrorKind::ReversedRepetitionBounds(min, max),
+		}),
+		_ => Ok(Token::Repeat(min, max)),
+	}
+}
+
+fn parse_class(
+	chars: &mut RegExpChars,
+	start: usize,
+	length: usize,
+	diagnostics: &mut Vec<Diagnostic>,
+) -> Result<Token, Diagnostic> {
+	let negated = chars.next_if(|&(c, _)| c == '^').is_some();
+	let mut class = BTreeSet::new();
+	let mut is_first = true;
+	loop {
+		let (first, first_position) = match chars.next() {
+			Some((']', _)) if !is_first => return Ok(Token::Class(negated, class)),
+			Some(('\\', position)) => (parse_escaped(chars, position)?, position),
+			Some((c, position)) => (c, position),
+			None => {
+				return Err(Diagnostic {
+					span: start..length,
+					kind: RegExpErrorKind::UnterminatedClass,
+				})
+			}
+		};
+		is_first = false;
+		let mut lookahead = chars.clone();
+		let last = match (lookahead.next(), lookahead.next()) {
+			(Some(('-', _)), Some((last, last_position))) if last != ']' => {
+				chars.nth(1);
+				let last = if last == '\\' {
+					parse_escaped(chars, last_position)?
+				} else {
+					last
+				};
+				if last < first {
+					diagnostics.push(Diagnostic {
+						span: first_position..chars.peek().map_or(length, |&(_, end)| end),
+						kind: RegExpErrorKind::ReversedRange(first, last),
+					});
+					continue;
+				}
+				last
+			}
+			_ => first,
+		};
+		if (first..=last).contains(&EPSILON) {
+			diagnostics.push(Diagnostic {
+				span: first_position..chars.peek().map_or(length, |&(_, end)| end),
+				kind: RegExpErrorKind::ReservedEpsilon,
+			});
+			continue;
+		}
+		class.extend(first..=last);
+	}
+}
+
+impl Parser {
+	fn report(&mut self, span: Range<usize>, kind: RegExpErrorKind) {
+		self.diagnostics.push(Diagnostic { span, kind });
+	}
+
+	fn at_operand_end(&mut self) -> bool {
+		matches!(
+			self.tokens.peek(),
+			None | Some((
+				_,
+				Token::Alternative | Token::Intersection | Token::EndGroup
+			))
+		)
+	}
+
+	fn parse_alternation(&mut self) -> RegExp {
+		let mut variants = vec![self.parse_intersection()];
+		while let Some((span, _)) = self
+			.tokens
+			.next_if(|(_, token)| matches!(token, Token::Alternative))
+		{
+			if matches!(self.tokens.peek(), None | Some((_, Token::EndGroup))) {
+				self.report(span, RegExpErrorKind::TrailingAlternative);
+			}
+			variants.push(self.parse_intersection());
+		}
+		if variants.len() == 1 {
+			variants.pop().unwrap()
+		} else {
+			RegExp::Alternation(variants)
+		}
+	}
+
+	fn parse_intersection(&mut self) -> RegExp {
+		let mut operands = vec![self.parse_concatenation()];
+		while self
+			.tokens
+			.next_if(|(_, token)| matches!(token, Token::Intersection))
+			.is_some()
+		{
+			operands.push(self.parse_concatenation());
+		}
+		if operands.len() == 1 {
+			operands.pop().unwrap()
+		} else {
+			RegExp::Intersection(operands)
+		}
+	}
+
+	fn parse_concatenation(&mut self) -> RegExp {
+		let mut parts = Vec::new();
+		while !self.at_operand_end() {
+			parts.push(self.parse_complement());
+		}
+		match parts.len() {
+			0 => RegExp::Epsilon,
+			1 => parts.pop().unwrap(),
+			_ => RegExp::Concat(parts),
+		}
+	}
+
+	fn parse_complement(&mut self) -> RegExp {
+		match self
+			.tokens
+			.next_if(|(_, token)| matches!(token, Token::Complement))
+		{
+			Some((span, _)) => {
+				if self.at_operand_end() {
+					self.report(span, RegExpErrorKind::MissingPrefixUnaryOpArg('~'));
+					RegExp::Epsilon
+				} else {
+					RegExp::Complement(Box::new(self.parse_complement()))
+				}
+			}
+			None => self.parse_repetition(),
+		}
+	}
+
+	fn parse_repetition(&mut self) -> RegExp {
+		let mut regexp = self.parse_atom();
+		while let Some((_, token)) = self.tokens.peek() {
+			regexp = match *token {
+				Token::Star => RegExp::Star(Box::new(regexp)),
+				Token::Plus => RegExp::Plus(Box::new(regexp)),
+				Token::Optional => RegExp::Optional(Box::new(regexp)),
+				Token::Repeat(min, max) => RegExp::Repeat(Box::new(regexp), min, max),
+				_ => break,
+			};
+			self.tokens.next();
+		}
+		regexp
+	}
+
+	fn parse_atom(&mut self) -> RegExp {
+		let (span, token) = self.tokens.next().unwrap();
+		let op = match token {
+			Token::Char(c) => return RegExp::Char(c),
+			Token::Class(negated, chars) => return RegExp::Class(negated, chars),
+			Token::Any => return RegExp::Any,
+			Token::Empty => return RegExp::Empty,
+			Token::StartGroup => {
+				let is_empty = matches!(self.tokens.peek(), Some((_, Token::EndGroup)));
+				let regexp = self.parse_alternation();
+				match self
+					.tokens
+					.next_if(|(_, token)| matches!(token, Token::EndGroup))
+				{
+					Some((end, _)) if is_empty => {
+						self.report(span.start..end.end, RegExpErrorKind::EmptyGroup)
+					}
+					Some(_) => {}
+					None => self.report(span, RegExpErrorKind::UnmatchedParenthesis),
+				}
+				return regexp;
+			}
+			Token::Star => '*',
+			Token::Plus => '+',
+			Token::Optional => '?',
+			Token::Repeat(_, _) => '{',
+			Token::EndGroup | Token::Alternative | Token::Intersection | Token::Complement => {
+				unreachable!()
+			}
+		};
+		self.report(span, RegExpErrorKind::MissingPostfixUnaryOpArg(op));
+		RegExp::Epsilon
+	}
+}
+
+impl fmt::Display for ParseRegExpError {
+	fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		for (index, diagnostic) in self.diagnostics.iter().enumerate() {
+			if index != 0 {
+				formatter.write_str("\n")?;
+			}
+			write!(formatter, "{}", diagnostic)?;
+		}
+		Ok(())
+	}
+}
+
+impl fmt::Display for Diagnostic {
+	fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			formatter,
+			"{}: {}: {}",
+			self.span.start + 1,
+			if self.is_warning() {
+				"warning"
+			} else {
+				"error"
+			},
+			self.kind
+		)
+	}
+}
+
+impl fmt::Display for RegExpErrorKind {
+	fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::MissingPostfixUnaryOpArg(op) => {
+				write!(formatter, "postfix operator '{}' takes 1 argument", op)
+			}
+			Self::MissingPrefixUnaryOpArg(op) => {
+				write!(formatter, "prefix operator '{}' takes 1 argument", op)
+			}
+			Self::UnmatchedParenthesis => formatter.write_str("unmatched parenthesis"),
+			Self::InvalidEscapeSequence => formatter.write_str("invalid escape sequence"),
+			Self::UnterminatedClass => formatter.write_str("unterminated character class"),
+			Self::ReversedRange(first, last) => {
+				write!(formatter, "range '{}-{}' is out of order", first, last)
+			}
+			Self::UnterminatedRepetition => formatter.write_str("unterminated repetition bounds"),
+			Self::InvalidRepetitionBound => formatter.write_str("invalid repetition bound"),
+			Self::ReservedEpsilon => {
+				write!(
+					formatter,
+					"'{}' is reserved for epsilon transitions",
+					EPSILON
+				)
+			}
+			Self::EmptyGroup => formatter.write_str("empty group"),
+			Self::TrailingAlternative => formatter.write_str("trailing alternative operator"),
+			Self::ReversedRepetitionBounds(min, max) => write!(
+				formatter,
+				"repetition bounds {{{},{}}} are out of order",
+				min, max
+			),
+		}
+	}
+}
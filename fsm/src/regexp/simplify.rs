@@ -0,0 +1,241 @@
+use super::RegExp;
+
+impl RegExp {
+	pub fn simplify(&self) -> Self {
+		match self {
+			Self::Empty | Self::Epsilon | Self::Char(_) | Self::Any => self.clone(),
+			Self::Class(false, chars) if chars.is_empty() => Self::Empty,
+			Self::Class(false, chars) if chars.len() == 1 => {
+				Self::Char(*chars.iter().next().unwrap())
+			}
+			Self::Class(_, _) => self.clone(),
+			Self::Concat(parts) => parts
+				.iter()
+				.map(Self::simplify)
+				.fold(Self::Epsilon, Self::concat),
+			Self::Alternation(variants) => variants
+				.iter()
+				.map(Self::simplify)
+				.reduce(Self::alternation)
+				.unwrap_or(Self::Empty),
+			Self::Intersection(operands) => operands
+				.iter()
+				.map(Self::simplify)
+				.reduce(Self::intersection)
+				.unwrap_or_else(|| Self::Star(Box::new(Self::Any))),
+			Self::Complement(inner) => inner.simplify().complement(),
+			Self::Star(inner) => inner.simplify().star(),
+			Self::Plus(inner) => inner.simplify().plus(),
+			Self::Optional(inner) => inner.simplify().optional(),
+			Self::Repeat(inner, min, max) => inner.simplify().repeat(*min, *max),
+		}
+	}
+
+	pub(crate) fn size(&self) -> usize {
+		match self {
+			Self::Empty | Self::Epsilon | Self::Char(_) | Self::Class(_, _) | Self::Any => 1,
+			Self::Concat(parts) | Self::Alternation(parts) | Self::Intersection(parts) => {
+				parts.iter().map(Self::size).sum::<usize>() + 1
+			}
+			Self::Complement(inner)
+			| Self::Star(inner)
+			| Self::Plus(inner)
+			| Self::Optional(inner)
+			| Self::Repeat(inner, _, _) => inner.size() + 1,
+		}
+	}
+
+	fn into_parts(self) -> Vec<Self> {
+		match self {
+			Self::Epsilon => Vec::new(),
+			Self::Concat(parts) => parts,
+			part => vec![part],
+		}
+	}
+
+	fn from_parts<I: IntoIterator<Item = Self>>(parts: I) -> Self {
+		parts.into_iter().fold(Self::Epsilon, Self::concat)
+	}
+
+	pub(crate) fn concat(self, rhs: Self) -> Self {
+		if self == Self::Empty || rhs == Self::Empty {
+			return Self::Empty;
+		}
+		let mut parts = self.into_parts();
+		for part in rhs.into_parts() {
+			if let Self::Star(inner) = &part {
+				if parts.last() == Some(&part) {
+					continue;
+				}
+				let repeated = inner.clone().into_parts();
+				if parts.ends_with(&repeated) {
+					parts.truncate(parts.len() - repeated.len());
+					parts.push(Self::Plus(inner.clone()));
+					continue;
+				}
+			}
+			parts.push(part);
+		}
+		match parts.len() {
+			0 => Self::Epsilon,
+			1 => parts.pop().unwrap(),
+			_ => Self::Concat(parts),
+		}
+	}
+
+	pub(crate) fn alternation(self, rhs: Self) -> Self {
+		let mut variants = Vec::new();
+		let mut has_epsilon = false;
+		let mut pending = vec![rhs, self];
+		while let Some(variant) = pending.pop() {
+			match variant {
+				Self::Empty => {}
+				Self::Epsilon => has_epsilon = true,
+				Self::Alternation(inner) => pending.extend(inner.into_iter().rev()),
+				Self::Optional(inner) => {
+					has_epsilon = true;
+					pending.push(*inner);
+				}
+				variant if !variants.contains(&variant) => variants.push(variant),
+				_ => {}
+			}
+		}
+		if has_epsilon {
+			if let Some(repeated) = variants
+				.iter()
+				.position(|variant| matches!(variant, Self::Star(_) | Self::Plus(_)))
+			{
+				variants[repeated] = variants[repeated].clone().star();
+				has_epsilon = false;
+			}
+		}
+		let alternation = Self::factor(variants);
+		if has_epsilon {
+			alternation.optional()
+		} else {
+			alternation
+		}
+	}
+
+	fn factor(mut variants: Vec<Self>) -> Self {
+		match variants.len() {
+			0 => return Self::Empty,
+			1 => return variants.pop().unwrap(),
+			_ => {}
+		}
+		let parts: Vec<Vec<Self>> = variants.iter().cloned().map(Self::into_parts).collect();
+		let shortest = parts.iter().map(Vec::len).min().unwrap_or_default();
+		let prefix = (0..shortest)
+			.take_while(|&index| parts.iter().all(|part| part[index] == parts[0][index]))
+			.count();
+		let suffix = (1..=shortest - prefix)
+			.take_while(|&index| {
+				let last = &parts[0][parts[0].len() - index];
+				parts.iter().all(|part| part[part.len() - index] == *last)
+			})
+			.count();
+		if prefix + suffix == 0 {
+			return Self::Alternation(variants);
+		}
+		let middle = parts
+			.iter()
+			.map(|part| Self::from_parts(part[prefix..part.len() - suffix].to_vec()))
+			.reduce(Self::alternation)
+			.unwrap();
+		let common = &parts[0];
+		Self::from_parts(
+			common[..prefix]
+				.iter()
+				.cloned()
+				.chain([middle])
+				.chain(common[common.len() - suffix..].iter().cloned()),
+		)
+	}
+
+	pub(crate) fn intersection(self, rhs: Self) -> Self {
+		let mut operands = Vec::new();
+		for operand in [self, rhs] {
+			let inner = match operand {
+				Self::Intersection(inner) => inner,
+				operand => vec![operand],
+			};
+			for operand in inner {
+				if operand == Self::Empty {
+					return Self::Empty;
+				}
+				if !operands.contains(&operand) {
+					operands.push(operand);
+				}
+			}
+		}
+		match operands.len() {
+			1 => operands.pop().unwrap(),
+			_ => Self::Intersection(operands),
+		}
+	}
+
+	pub(crate) fn complement(self) -> Self {
+		match self {
+			Self::Complement(inner) => *inner,
+			_ => Self::Complement(Box::new(self)),
+		}
+	}
+
+	pub(crate) fn star(self) -> Self {
+		match self {
+			Self::Empty | Self::Epsilon => Self::Epsilon,
+			Self::Star(_) => self,
+			Self::Plus(inner) | Self::Optional(inner) => inner.star(),
+			Self::Alternation(variants) => {
+				let mut variants: Vec<Self> = variants
+					.into_iter()
+					.filter(|variant| *variant != Self::Epsilon)
+					.map(|variant| match variant {
+						Self::Star(inner) | Self::Plus(inner) | Self::Optional(inner) => *inner,
+						variant => variant,
+					})
+					.collect();
+				match variants.len() {
+					0 => Self::Epsilon,
+					1 => variants.pop().unwrap().star(),
+					_ => Self::Star(Box::new(Self::Alternation(variants))),
+				}
+			}
+			_ => Self::Star(Box::new(self)),
+		}
+	}
+
+	pub(crate) fn plus(self) -> Self {
+		match self {
+			Self::Empty | Self::Epsilon | Self::Star(_) | Self::Plus(_) => self,
+			Self::Optional(inner) => inner.star(),
+			_ => Self::Plus(Box::new(self)),
+		}
+	}
+
+	pub(crate) fn optional(self) -> Self {
+		match self {
+			Self::Empty | Self::Epsilon => Self::Epsilon,
+			Self::Star(_) | Self::Optional(_) => self,
+			Self::Plus(inner) => inner.star(),
+			_ => Self::Optional(Box::new(self)),
+		}
+	}
+
+	pub(crate) fn repeat(self, min: usize, max: Option<usize>) -> Self {
+		match (min, max) {
+			(min, Some(max)) if max < min => Self::Empty,
+			(_, Some(0)) => Self::Epsilon,
+			(0, Some(1)) => self.optional(),
+			(1, Some(1)) => self,
+			(0, None) => self.star(),
+			(1, None) => self.plus(),
+			_ => match self {
+				Self::Epsilon => Self::Epsilon,
+				Self::Empty if min == 0 => Self::Epsilon,
+				Self::Empty => Self::Empty,
+				_ => Self::Repeat(Box::new(self), min, max),
+			},
+		}
+	}
+}
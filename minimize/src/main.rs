@@ -3,7 +3,8 @@ use std::io;
 
 fn main() {
 	if let Err(err) = (|| {
-		DFA::load_from_simple_text(&mut io::stdin().lock())?
+		DFA::load_from_simple_text(&mut io::stdin().lock())
+			.map_err(|err| err.with_file("{stdin}"))?
 			.reachable_from(0)
 			.minimize()
 			.store_as_simple_text(&mut io::stdout())
@@ -0,0 +1,61 @@
+use fsm::Automaton;
+use std::fs::File;
+use std::io::{self, BufReader};
+
+fn load(path: Option<String>, suffixed: bool) -> io::Result<Automaton> {
+	let path = path.ok_or_else(|| {
+		io::Error::new(
+			io::ErrorKind::InvalidInput,
+			"usage: nfa-equiv [--suffixed] [--inclusion] <automaton> <automaton> | nfa-equiv [--suffixed] --universal <automaton>",
+		)
+	})?;
+	Automaton::load(&mut BufReader::new(File::open(&path)?), suffixed)
+		.map_err(|err| err.with_file(&path))
+}
+
+fn main() {
+	if let Err(err) = (|| -> io::Result<()> {
+		let mut args = std::env::args().skip(1).peekable();
+		let suffixed = args.next_if(|arg| arg == "--suffixed").is_some();
+		match args
+			.next_if(|arg| arg == "--inclusion" || arg == "--universal")
+			.as_deref()
+		{
+			Some("--inclusion") => {
+				let lhs = load(args.next(), suffixed)?;
+				let rhs = load(args.next(), suffixed)?;
+				match lhs.inclusion_counterexample(&rhs) {
+					Some(word) => println!(
+						"not included: {:?} is accepted by the first automaton only",
+						word
+					),
+					None => println!("included"),
+				}
+			}
+			Some(_) => match load(args.next(), suffixed)?.universality_counterexample() {
+				Some(word) => println!("not universal: {:?} is rejected", word),
+				None => println!("universal"),
+			},
+			None => {
+				let lhs = load(args.next(), suffixed)?;
+				let rhs = load(args.next(), suffixed)?;
+				match lhs.equivalence_counterexample(&rhs) {
+					Some(word) => println!(
+						"not equivalent: {:?} is accepted by the {} automaton only",
+						word,
+						if lhs.accepts(&word) {
+							"first"
+						} else {
+							"second"
+						}
+					),
+					None => println!("equivalent"),
+				}
+			}
+		}
+		Ok(())
+	})() {
+		eprintln!("Error: {}", err);
+		std::process::exit(1);
+	}
+}
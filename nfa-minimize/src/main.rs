@@ -0,0 +1,28 @@
+use fsm::Automaton;
+use std::io;
+
+fn main() {
+	if let Err(err) = (|| {
+		let mut args = std::env::args().skip(1).peekable();
+		let suffixed = args.next_if(|arg| arg == "--suffixed").is_some();
+		let moore = args.next_if(|arg| arg == "--moore").is_some();
+		if args.next().is_some() {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"usage: nfa-minimize [--suffixed] [--moore]",
+			));
+		}
+		let automaton = Automaton::load(&mut io::stdin().lock(), suffixed)
+			.map_err(|err| err.with_file("{stdin}"))?;
+		let automaton = if moore {
+			let (dfa, inputs) = automaton.to_moore();
+			Automaton::from_moore(&dfa.reachable_from(0).minimize(), &inputs)?
+		} else {
+			automaton.minimize_brzozowski()
+		};
+		automaton.store(&mut io::stdout(), suffixed)
+	})() {
+		eprintln!("Error: {}", err);
+		std::process::exit(1);
+	}
+}
@@ -0,0 +1,36 @@
+use fsm::Automaton;
+use std::fs::File;
+use std::io::{self, BufReader};
+
+fn usage() -> io::Error {
+	io::Error::new(
+		io::ErrorKind::InvalidInput,
+		"usage: nfa-product [--suffixed] --intersect | --union | --difference | --symmetric-difference <automaton> <automaton>",
+	)
+}
+
+fn load(path: Option<String>, suffixed: bool) -> io::Result<Automaton> {
+	let path = path.ok_or_else(usage)?;
+	Automaton::load(&mut BufReader::new(File::open(&path)?), suffixed)
+		.map_err(|err| err.with_file(&path))
+}
+
+fn main() {
+	if let Err(err) = (|| {
+		let mut args = std::env::args().skip(1).peekable();
+		let suffixed = args.next_if(|arg| arg == "--suffixed").is_some();
+		let operation = match args.next().as_deref() {
+			Some("--intersect") => Automaton::intersect,
+			Some("--union") => Automaton::union,
+			Some("--difference") => Automaton::difference,
+			Some("--symmetric-difference") => Automaton::symmetric_difference,
+			_ => return Err(usage()),
+		};
+		let lhs = load(args.next(), suffixed)?;
+		let rhs = load(args.next(), suffixed)?;
+		operation(&lhs, &rhs).store(&mut io::stdout(), suffixed)
+	})() {
+		eprintln!("Error: {}", err);
+		std::process::exit(1);
+	}
+}
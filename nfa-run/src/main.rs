@@ -0,0 +1,32 @@
+use fsm::Automaton;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+
+fn main() {
+	if let Err(err) = (|| -> io::Result<()> {
+		let mut args = std::env::args().skip(1).peekable();
+		let suffixed = args.next_if(|arg| arg == "--suffixed").is_some();
+		let path = args.next().ok_or_else(|| {
+			io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"usage: nfa-run [--suffixed] <automaton>",
+			)
+		})?;
+		let automaton = Automaton::load(&mut BufReader::new(File::open(&path)?), suffixed)
+			.map_err(|err| err.with_file(&path))?;
+		let mut stdout = io::stdout();
+		for word in io::stdin().lock().lines() {
+			let word = word?;
+			let verdict = if automaton.accepts(&word) {
+				"accepted"
+			} else {
+				"rejected"
+			};
+			writeln!(stdout, "{}: {}", word, verdict)?;
+		}
+		Ok(())
+	})() {
+		eprintln!("Error: {}", err);
+		std::process::exit(1);
+	}
+}
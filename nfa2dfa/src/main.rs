@@ -3,9 +3,18 @@ use std::io;
 
 fn main() {
 	if let Err(err) = (|| {
-		Automaton::load_from_simple_text(&mut io::stdin().lock())?
+		let mut args = std::env::args().skip(1).peekable();
+		let suffixed = args.next_if(|arg| arg == "--suffixed").is_some();
+		if args.next().is_some() {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"usage: nfa2dfa [--suffixed]",
+			));
+		}
+		Automaton::load(&mut io::stdin().lock(), suffixed)
+			.map_err(|err| err.with_file("{stdin}"))?
 			.determine()
-			.store_as_simple_text(&mut io::stdout())
+			.store(&mut io::stdout(), suffixed)
 	})() {
 		eprintln!("Error: {}", err);
 		std::process::exit(1);
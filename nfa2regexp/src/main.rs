@@ -0,0 +1,23 @@
+use fsm::Automaton;
+use std::io;
+
+fn main() {
+	if let Err(err) = (|| {
+		let mut args = std::env::args().skip(1).peekable();
+		let suffixed = args.next_if(|arg| arg == "--suffixed").is_some();
+		if args.next().is_some() {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"usage: nfa2regexp [--suffixed]",
+			));
+		}
+		let regexp = Automaton::load(&mut io::stdin().lock(), suffixed)
+			.map_err(|err| err.with_file("{stdin}"))?
+			.to_regexp();
+		println!("{}", regexp);
+		Ok(())
+	})() {
+		eprintln!("Error: {}", err);
+		std::process::exit(1);
+	}
+}
@@ -0,0 +1,42 @@
+use fsm::RegExp;
+use std::io;
+
+fn main() {
+	if let Err(err) = (|| {
+		let extended = match std::env::args().nth(1).as_deref() {
+			Some("--extended") => true,
+			None => false,
+			Some(_) => {
+				return Err(io::Error::new(
+					io::ErrorKind::InvalidInput,
+					"usage: regexp-simplify [--extended]",
+				))
+			}
+		};
+		let mut line = String::new();
+		io::stdin().read_line(&mut line)?;
+		let regexp = line.trim_end();
+		let regexp = match RegExp::parse_with_warnings(regexp, extended) {
+			Ok((parsed, warnings)) => {
+				for warning in warnings {
+					eprintln!("{}", warning.render(regexp, "{stdin}:1"));
+				}
+				parsed
+			}
+			Err(err) => {
+				for diagnostic in err.diagnostics() {
+					eprintln!("{}", diagnostic.render(regexp, "{stdin}:1"));
+				}
+				return Err(io::Error::new(
+					io::ErrorKind::InvalidData,
+					"invalid regular expression",
+				));
+			}
+		};
+		println!("{}", regexp.simplify());
+		Ok(())
+	})() {
+		eprintln!("Error: {}", err);
+		std::process::exit(1);
+	}
+}
@@ -1,15 +1,190 @@
-use fsm::Automaton;
-use std::io;
+use fsm::{Automaton, RegExp, EPSILON};
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+
+enum Construction {
+	Glushkov,
+	Brzozowski,
+	Antimirov,
+	Thompson,
+}
+
+struct Options {
+	alphabet: Vec<char>,
+	extended: bool,
+	construction: Construction,
+	suffixed: bool,
+	named: bool,
+}
+
+fn usage() -> io::Error {
+	io::Error::new(
+		io::ErrorKind::InvalidInput,
+		"usage: regexp2nfa [--extended] [--alphabet <chars>] [--brzozowski | --antimirov | --thompson] [--suffixed] [--named] [--output-dir <dir>] [<file>]",
+	)
+}
+
+fn split_name(line: &str) -> Option<(&str, usize)> {
+	let (name, regexp) = line.split_once('=')?;
+	let name = name.trim();
+	if !name.is_empty()
+		&& name
+			.chars()
+			.all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+	{
+		Some((name, line.len() - regexp.trim_start().len()))
+	} else {
+		None
+	}
+}
+
+impl Options {
+	fn compile(&self, line: &str, start: usize, location: &str) -> io::Result<Automaton> {
+		let offset = line[..start].chars().count();
+		let regexp = match RegExp::parse_with_warnings(&line[start..], self.extended) {
+			Ok((parsed, warnings)) => {
+				for mut warning in warnings {
+					warning.shift(offset);
+					eprintln!("{}", warning.render(line, location));
+				}
+				parsed
+			}
+			Err(err) => {
+				for mut diagnostic in err.into_diagnostics() {
+					diagnostic.shift(offset);
+					eprintln!("{}", diagnostic.render(line, location));
+				}
+				return Err(io::Error::new(
+					io::ErrorKind::InvalidData,
+					"invalid regular expression",
+				));
+			}
+		};
+		Ok(match self.construction {
+			Construction::Glushkov => Automaton::from_parsed_regexp(&regexp, &self.alphabet),
+			Construction::Brzozowski => Automaton::from_regexp_brzozowski(&regexp, &self.alphabet),
+			Construction::Antimirov => Automaton::from_regexp_antimirov(&regexp, &self.alphabet),
+			Construction::Thompson => Automaton::from_regexp_thompson(&regexp, &self.alphabet),
+		})
+	}
+
+	fn compile_batch<R: BufRead>(
+		&self,
+		reader: R,
+		file_name: &str,
+		output_dir: Option<&PathBuf>,
+	) -> io::Result<()> {
+		let mut n_failed = 0;
+		let mut names = BTreeSet::new();
+		let mut stdout = io::stdout();
+		for (line_number, line) in (1..).zip(reader.lines()) {
+			let line = line?;
+			let line = line.trim_end();
+			if line.is_empty() {
+				continue;
+			}
+			let location = format!("{}:{}", file_name, line_number);
+			let (name, start) = if self.named {
+				match split_name(line) {
+					Some((name, start)) if names.insert(name.to_string()) => {
+						(name.to_string(), start)
+					}
+					Some((name, _)) => {
+						eprintln!("{}:1: error: duplicate name '{}'", location, name);
+						n_failed += 1;
+						continue;
+					}
+					None => {
+						eprintln!("{}:1: error: expected 'name = regexp'", location);
+						n_failed += 1;
+						continue;
+					}
+				}
+			} else {
+				(line_number.to_string(), 0)
+			};
+			let automaton = match self.compile(line, start, &location) {
+				Ok(automaton) => automaton,
+				Err(_) => {
+					n_failed += 1;
+					continue;
+				}
+			};
+			match output_dir {
+				Some(dir) => {
+					let mut writer =
+						BufWriter::new(File::create(dir.join(format!("{}.nfa", name)))?);
+					automaton.store(&mut writer, self.suffixed)?;
+					writer.flush()?;
+				}
+				None => {
+					writeln!(stdout, "--- {}", name)?;
+					automaton.store(&mut stdout, self.suffixed)?;
+				}
+			}
+		}
+		if n_failed == 0 {
+			Ok(())
+		} else {
+			Err(io::Error::new(
+				io::ErrorKind::InvalidData,
+				format!("{} invalid line(s)", n_failed),
+			))
+		}
+	}
+}
 
 fn main() {
 	if let Err(err) = (|| {
-		let mut line = String::new();
-		io::stdin().read_line(&mut line)?;
-		Automaton::from_regexp(line.trim_end())
-			.map_err(|err| {
-				io::Error::new(io::ErrorKind::InvalidData, format!("{{stdin}}:1:{}", err))
-			})?
-			.store_as_simple_text(&mut io::stdout())
+		let mut options = Options {
+			alphabet: Vec::new(),
+			extended: false,
+			construction: Construction::Glushkov,
+			suffixed: false,
+			named: false,
+		};
+		let mut output_dir = None;
+		let mut input = None;
+		let mut args = std::env::args().skip(1);
+		while let Some(arg) = args.next() {
+			match arg.as_str() {
+				"--alphabet" => {
+					let alphabet = args.next().ok_or_else(usage)?;
+					if alphabet.contains(EPSILON) {
+						return Err(io::Error::new(
+							io::ErrorKind::InvalidInput,
+							format!("'{}' can't be used in alphabet", EPSILON),
+						));
+					}
+					options.alphabet.extend(alphabet.chars());
+				}
+				"--extended" => options.extended = true,
+				"--brzozowski" => options.construction = Construction::Brzozowski,
+				"--antimirov" => options.construction = Construction::Antimirov,
+				"--thompson" => options.construction = Construction::Thompson,
+				"--suffixed" => options.suffixed = true,
+				"--named" => options.named = true,
+				"--output-dir" => output_dir = Some(PathBuf::from(args.next().ok_or_else(usage)?)),
+				_ if input.is_none() && (arg == "-" || !arg.starts_with('-')) => input = Some(arg),
+				_ => return Err(usage()),
+			}
+		}
+		match input.as_deref() {
+			Some("-") => options.compile_batch(io::stdin().lock(), "{stdin}", output_dir.as_ref()),
+			Some(path) => {
+				options.compile_batch(BufReader::new(File::open(path)?), path, output_dir.as_ref())
+			}
+			None if output_dir.is_some() => Err(usage()),
+			None => {
+				let mut line = String::new();
+				io::stdin().read_line(&mut line)?;
+				options
+					.compile(line.trim_end(), 0, "{stdin}:1")?
+					.store(&mut io::stdout(), options.suffixed)
+			}
+		}
 	})() {
 		eprintln!("Error: {}", err);
 		std::process::exit(1);
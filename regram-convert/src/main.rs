@@ -0,0 +1,11 @@
+use fsm::Automaton;
+use std::io;
+
+fn main() {
+	if let Err(err) = Automaton::convert_regular_grammar(&mut io::stdin().lock(), &mut io::stdout())
+		.map_err(|err| err.with_file("{stdin}"))
+	{
+		eprintln!("Error: {}", err);
+		std::process::exit(1);
+	}
+}
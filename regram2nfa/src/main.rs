@@ -3,8 +3,17 @@ use std::io;
 
 fn main() {
 	if let Err(err) = (|| {
-		Automaton::load_from_regular_grammar(&mut io::stdin().lock())?
-			.store_as_simple_text(&mut io::stdout())
+		let mut args = std::env::args().skip(1).peekable();
+		let suffixed = args.next_if(|arg| arg == "--suffixed").is_some();
+		if args.next().is_some() {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"usage: regram2nfa [--suffixed]",
+			));
+		}
+		Automaton::load_from_regular_grammar(&mut io::stdin().lock())
+			.map_err(|err| err.with_file("{stdin}"))?
+			.store(&mut io::stdout(), suffixed)
 	})() {
 		eprintln!("Error: {}", err);
 		std::process::exit(1);